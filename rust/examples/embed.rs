@@ -0,0 +1,70 @@
+//! Lint a Python snippet from Rust and print the diagnostics.
+//!
+//! Run with `cargo run --example embed`.
+
+use _rust_checker::{DiagnosticSink, Linter, Rule, RuleContext, SchemaCollected};
+use std::path::Path;
+
+const SOURCE: &str = r#"
+from typedframes import BaseSchema, Column
+
+class UserSchema(BaseSchema):
+    user_id = Column(type=int)
+    email = Column(type=str)
+
+def load() -> DataFrame[UserSchema]:
+    ...
+
+df = load()
+df["emial"]
+"#;
+
+/// Example custom rule: warn about schemas with a single column.
+struct SingleColumnSchema;
+
+impl Rule for SingleColumnSchema {
+    fn name(&self) -> &str {
+        "single-column-schema"
+    }
+
+    fn on_schema_collected(
+        &self,
+        _ctx: &RuleContext,
+        schema: &SchemaCollected,
+        sink: &mut DiagnosticSink,
+    ) {
+        if schema.columns.len() == 1 {
+            sink.warning(
+                schema.line,
+                schema.col,
+                "single-column-schema",
+                format!("Schema '{}' declares a single column", schema.name),
+            );
+        }
+    }
+}
+
+fn main() -> anyhow::Result<()> {
+    let mut linter = Linter::builder()
+        .explain_findings(true)
+        .rule(Box::new(SingleColumnSchema))
+        .build();
+
+    for error in linter.check_file_internal(SOURCE, Path::new("example.py"))? {
+        println!(
+            "example.py:{}:{}: {}[{}] {}",
+            error.line, error.col, error.severity, error.code, error.message
+        );
+        if let Some(explanation) = &error.explanation {
+            println!(
+                "  = note: '{}' bound via {} at line {}",
+                explanation.variable, explanation.bound_via, explanation.bound_at
+            );
+        }
+    }
+
+    // One-shot form using the default rules.
+    let errors = _rust_checker::check_source(SOURCE, Path::new("example.py"))?;
+    println!("{} finding(s) with the default rules", errors.len());
+    Ok(())
+}
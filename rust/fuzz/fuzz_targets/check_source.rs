@@ -0,0 +1,15 @@
+//! Feed arbitrary source through the full check path; any panic is a bug.
+//!
+//! Run with `cargo +nightly fuzz run check_source` from `rust/`.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use std::path::Path;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(source) = std::str::from_utf8(data) {
+        // Parse errors are expected for most inputs; only panics matter.
+        let _ = _rust_checker::check_source(source, Path::new("fuzz.py"));
+    }
+});
@@ -1,8 +1,6 @@
-use _rust_checker::{find_project_root, is_enabled, Linter};
-use anyhow::Context;
+use _rust_checker::check_path;
 use anyhow::Result;
 use std::env;
-use std::fs;
 use std::path::Path;
 
 fn main() -> Result<()> {
@@ -12,18 +10,7 @@ fn main() -> Result<()> {
         return Ok(());
     }
 
-    let path = Path::new(&args[1]);
-    let project_root = find_project_root(path);
-
-    if !is_enabled(&project_root) {
-        println!("[]");
-        return Ok(());
-    }
-
-    let source =
-        fs::read_to_string(path).with_context(|| format!("Failed to read file: {:?}", path))?;
-    let mut linter = Linter::new();
-    let errors = linter.check_file_internal(&source, path)?;
+    let errors = check_path(Path::new(&args[1]))?;
 
     println!("{}", serde_json::to_string(&errors)?);
 
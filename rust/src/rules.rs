@@ -0,0 +1,626 @@
+//! Pluggable lint rules.
+//!
+//! The [`Linter`] walks the AST and maintains schema/variable state; *rules* observe
+//! that walk through a small set of events and report diagnostics.  Each event carries
+//! a [`RuleContext`] (read-only view of the linter state at that point in the walk) and
+//! a [`DiagnosticSink`] to push [`LintError`]s into.
+//!
+//! Events:
+//!
+//! * [`Rule::on_schema_collected`] — a schema class body has been fully parsed and its
+//!   (deduplicated) column list registered.
+//! * [`Rule::on_variable_bound`] — a variable was (re)bound to a schema, e.g. by an
+//!   annotation, a load call, or a method-chain result.
+//! * [`Rule::on_column_accessed`] — `df.col`, `df["col"]` or `df.loc[rows, "col"]` was
+//!   read (or a column named in `df.schema == {...}`) on a variable with a known
+//!   schema.  Reserved method names (`df.groupby`, …) are not column accesses and
+//!   never reach this hook.
+//! * [`Rule::on_row_filter`] — a tracked frame was indexed with a row condition,
+//!   `df[cond]` or `df.loc[cond, ...]`.
+//! * [`Rule::on_stmt_visited`] — called for every statement before the linter updates
+//!   its own state for it, including statements nested in function bodies.
+//!
+//! The built-in checks that are expressed as rules live in [`default_rules`] and are
+//! installed by [`Linter::new`]; [`opt_in_rules`] are only installed when enabled by
+//! name with the `enable` config key.  Embedders add their own via [`Linter::add_rule`] or
+//! [`LinterBuilder::rule`](crate::LinterBuilder::rule):
+//!
+//! ```ignore
+//! let mut linter = Linter::builder().rule(Box::new(RequireLoadTs)).build();
+//! let errors = linter.check_file_internal(source, path)?;
+//! ```
+
+use crate::{
+    find_best_match, quote_column, LintError, Linter, CODE_CONDITIONAL_COLUMN,
+    CODE_MASK_PRECEDENCE, CODE_RESERVED_NAME, CODE_SCHEMA_MISMATCH, CODE_UNKNOWN_COLUMN,
+    RESERVED_METHODS,
+};
+use ruff_python_ast::{self as ast, Expr, Stmt};
+
+/// A schema class whose columns have just been collected.
+#[non_exhaustive]
+pub struct SchemaCollected<'a> {
+    /// Class name, e.g. `"UserSchema"`.
+    pub name: &'a str,
+    /// Sorted, deduplicated column names (including inherited ones).
+    pub columns: &'a [String],
+    /// 1-indexed line of the `class` statement.
+    pub line: usize,
+    /// 1-indexed column of the `class` statement.
+    pub col: usize,
+}
+
+/// A variable that has just been bound to a schema.
+#[non_exhaustive]
+pub struct VariableBound<'a> {
+    pub variable: &'a str,
+    /// Schema name; inferred schemas start with `__inferred_`.
+    pub schema: &'a str,
+    /// 1-indexed line of the binding.
+    pub line: usize,
+    /// How the binding came about, e.g. `"annotation"`, `"read_csv"`, `"drop"`.
+    pub via: &'a str,
+}
+
+/// How a column was read off a tracked variable.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum AccessKind {
+    /// `df.col`
+    Attribute,
+    /// `df["col"]`
+    Subscript,
+    /// `df.loc[rows, "col"]` / `df.at[row, "col"]`
+    Label,
+    /// A name in an expected-schema literal: `df.schema == {"col": pl.Int64}`
+    Introspection,
+    /// A column-naming method argument: `df.drop_duplicates(subset=["col"])`
+    Argument,
+    /// A key of a per-column dict argument: `df.fillna({"col": 0})`.  `effect` says what
+    /// an unknown key does at runtime, e.g. "astype raises KeyError".
+    DictKey { effect: &'static str },
+    /// A name inside the expression string of `df.query("col > 1")` / `df.eval(...)`;
+    /// `method` is `"query"` or `"eval"`.
+    Expression { method: &'static str },
+}
+
+/// A column read on a variable whose schema is known.
+#[non_exhaustive]
+pub struct ColumnAccess<'a> {
+    pub variable: &'a str,
+    pub column: &'a str,
+    pub schema: &'a str,
+    /// Line at which `variable` was bound to `schema`.
+    pub defined_line: usize,
+    /// 1-indexed location of the access expression.
+    pub line: usize,
+    pub col: usize,
+    /// 1-indexed end of the access expression, exclusive.
+    pub end_line: usize,
+    pub end_col: usize,
+    pub kind: AccessKind,
+}
+
+/// A tracked frame indexed with a row condition: `df[cond]` or `df.loc[cond, ...]`.
+/// Only comparisons, binary operations such as `m1 & m2`, and negations are reported.
+#[non_exhaustive]
+pub struct RowFilter<'a> {
+    pub variable: &'a str,
+    pub schema: &'a str,
+    /// The condition itself: the whole slice of `df[...]`, the row part of `.loc`.
+    pub condition: &'a Expr,
+}
+
+/// Read-only view of the linter state handed to rule callbacks.
+pub struct RuleContext<'a> {
+    linter: &'a Linter,
+}
+
+impl<'a> RuleContext<'a> {
+    pub(crate) fn new(linter: &'a Linter) -> Self {
+        Self { linter }
+    }
+
+    /// Does `schema` contain `column`?  Also true for names matched by an open prefix
+    /// pattern, such as the dummy columns produced by `pd.get_dummies`.
+    pub fn has_column(&self, schema: &str, column: &str) -> bool {
+        self.linter.schema_has_column(schema, column)
+    }
+
+    /// Line of the `if` under which `column` was added to `schema`, when it was added in
+    /// only some branches and so may be missing after them.
+    pub fn conditional_column(&self, schema: &str, column: &str) -> Option<usize> {
+        self.linter
+            .state
+            .conditional_columns
+            .get(schema)
+            .and_then(|cols| cols.get(column))
+            .copied()
+    }
+
+    /// Known columns of `schema`, if it is registered.
+    pub fn columns(&self, schema: &str) -> Option<&'a [String]> {
+        self.linter.state.schemas.get(schema).map(|c| c.as_slice())
+    }
+
+    /// The `(schema_name, defined_line)` a variable is currently bound to.
+    pub fn variable_schema(&self, variable: &str) -> Option<(&'a str, usize)> {
+        self.linter
+            .state
+            .variables
+            .get(variable)
+            .map(|(s, l)| (s.as_str(), *l))
+    }
+
+    /// Schema a function is known to return, from its return annotation or inferred
+    /// from a `return <var>` in its body.
+    pub fn function_schema(&self, function: &str) -> Option<&'a str> {
+        self.linter
+            .state
+            .functions
+            .get(function)
+            .map(|s| s.as_str())
+    }
+
+    /// Schema named by a frame annotation such as `DataFrame[S]` or
+    /// `Annotated[pd.DataFrame, S]` (quoted or not), honouring import aliases and the
+    /// configured `frame-types`.
+    pub fn annotation_schema(&self, annotation: &Expr) -> Option<String> {
+        self.linter.annotation_schema(annotation)
+    }
+
+    /// Schema a value names for itself and how: `("S", "from_schema")` for
+    /// `PandasFrame.from_schema(raw, S)`, `("S", "cast")` for `cast(DataFrame[S], raw)`.
+    pub fn stated_schema(&self, value: &Expr) -> Option<(String, &'static str)> {
+        self.linter.stated_schema(value)
+    }
+
+    /// Evidence trail for `variable` when `--explain-findings` is enabled, else `None`.
+    pub fn explain(&self, variable: &str) -> Option<crate::Explanation> {
+        self.linter.explain_variable(variable)
+    }
+
+    /// How `variable` was bound, e.g. `"annotation"` or `"function return"`.
+    pub fn bound_via(&self, variable: &str) -> Option<&'a str> {
+        self.linter
+            .state
+            .binding_via
+            .get(variable)
+            .map(|s| s.as_str())
+    }
+
+    /// Message suffix pointing a finding on `variable` at the return annotation of the
+    /// function it was bound from; `None` unless it was bound via a function return.
+    pub fn provenance_hint(&self, variable: &str) -> Option<String> {
+        self.linter.provenance_hint(variable)
+    }
+
+    /// Line of the `drop(...)` that removed `column` from an ancestor of `schema`, if any.
+    pub fn dropped_at(&self, schema: &str, column: &str) -> Option<usize> {
+        self.linter.dropped_at(schema, column)
+    }
+
+    /// Human-readable schema description, as used in the built-in messages.
+    pub fn describe_schema(&self, schema: &str, defined_line: usize) -> String {
+        self.linter.schema_display(schema, defined_line)
+    }
+
+    /// 1-indexed `(line, col)` of a node's start.
+    pub fn location(&self, node: &impl ruff_text_size::Ranged) -> (usize, usize) {
+        self.linter.source_location(node.range().start())
+    }
+
+    /// Name of the innermost function whose body is being visited.
+    pub fn enclosing_function(&self) -> Option<&'a str> {
+        self.linter.scope.last().map(|s| s.as_str())
+    }
+
+    /// `(schema, annotation source)` of the innermost enclosing function's return
+    /// annotation, e.g. `("OrderSchema", "PandasFrame[OrderSchema]")`.
+    pub fn declared_return(&self) -> Option<(&'a str, &'a str)> {
+        self.linter
+            .return_schemas
+            .last()?
+            .as_ref()
+            .map(|(schema, annotation)| (schema.as_str(), annotation.as_str()))
+    }
+
+    /// Path of the file being linted, as passed to `check_file_internal`.
+    pub fn file_display(&self) -> &'a str {
+        &self.linter.file_display
+    }
+}
+
+/// Collects diagnostics reported by rules.
+pub struct DiagnosticSink<'a> {
+    errors: &'a mut Vec<LintError>,
+}
+
+impl<'a> DiagnosticSink<'a> {
+    pub(crate) fn new(errors: &'a mut Vec<LintError>) -> Self {
+        Self { errors }
+    }
+
+    pub fn push(&mut self, error: LintError) {
+        self.errors.push(error);
+    }
+
+    pub fn error(&mut self, line: usize, col: usize, code: &str, message: String) {
+        self.push(LintError {
+            line,
+            col,
+            code: code.to_string(),
+            message,
+            severity: "error".to_string(),
+            ..Default::default()
+        });
+    }
+
+    pub fn warning(&mut self, line: usize, col: usize, code: &str, message: String) {
+        self.push(LintError {
+            line,
+            col,
+            code: code.to_string(),
+            message,
+            severity: "warning".to_string(),
+            ..Default::default()
+        });
+    }
+}
+
+/// A lint rule driven by the linter's AST walk.  Every callback defaults to a no-op,
+/// so a rule only implements the events it cares about.
+pub trait Rule {
+    /// Short identifier, e.g. `"missing-column"`.  Used for registration/debugging;
+    /// diagnostic codes are chosen per report.
+    fn name(&self) -> &str;
+
+    fn on_schema_collected(
+        &self,
+        _ctx: &RuleContext,
+        _schema: &SchemaCollected,
+        _sink: &mut DiagnosticSink,
+    ) {
+    }
+
+    fn on_variable_bound(
+        &self,
+        _ctx: &RuleContext,
+        _binding: &VariableBound,
+        _sink: &mut DiagnosticSink,
+    ) {
+    }
+
+    fn on_column_accessed(
+        &self,
+        _ctx: &RuleContext,
+        _access: &ColumnAccess,
+        _sink: &mut DiagnosticSink,
+    ) {
+    }
+
+    fn on_row_filter(&self, _ctx: &RuleContext, _filter: &RowFilter, _sink: &mut DiagnosticSink) {}
+
+    fn on_stmt_visited(&self, _ctx: &RuleContext, _stmt: &Stmt, _sink: &mut DiagnosticSink) {}
+}
+
+/// The rules installed by [`Linter::new`].
+pub fn default_rules() -> Vec<Box<dyn Rule>> {
+    vec![
+        Box::new(MissingColumnRule),
+        Box::new(ReservedNameRule),
+        Box::new(SchemaMismatchRule),
+        Box::new(ConditionalColumnRule),
+    ]
+}
+
+/// Rules that are off by default.  Each is installed when its [`Rule::name`] is listed
+/// in the `enable` config key.
+pub fn opt_in_rules() -> Vec<Box<dyn Rule>> {
+    vec![Box::new(MaskPrecedenceRule)]
+}
+
+/// Reports `unknown-column` when `df.col` / `df["col"]` names a column the bound
+/// schema does not declare, with a "did you mean" hint for near misses.
+pub struct MissingColumnRule;
+
+impl Rule for MissingColumnRule {
+    fn name(&self) -> &str {
+        "missing-column"
+    }
+
+    fn on_column_accessed(
+        &self,
+        ctx: &RuleContext,
+        access: &ColumnAccess,
+        sink: &mut DiagnosticSink,
+    ) {
+        let Some(columns) = ctx.columns(access.schema) else {
+            return;
+        };
+        if ctx.has_column(access.schema, access.column) {
+            return;
+        }
+        let mut message = format!(
+            "Column {} does not exist in {}",
+            quote_column(access.column),
+            ctx.describe_schema(access.schema, access.defined_line)
+        );
+        match access.kind {
+            AccessKind::DictKey { effect } => message.push_str(&format!(", so {effect}")),
+            AccessKind::Expression { method } => {
+                message.push_str(&format!(" (referenced in the {method} string)"))
+            }
+            _ => {}
+        }
+        if let Some(line) = ctx.dropped_at(access.schema, access.column) {
+            message.push_str(&format!(" — it was dropped at line {line}"));
+        } else if let Some(suggestion) = find_best_match(access.column, columns) {
+            message.push_str(&format!(" (did you mean {}?)", quote_column(suggestion)));
+        }
+        if let Some(hint) = ctx.provenance_hint(access.variable) {
+            message.push_str(&hint);
+        }
+        sink.push(LintError {
+            line: access.line,
+            col: access.col,
+            code: CODE_UNKNOWN_COLUMN.to_string(),
+            message,
+            severity: "error".to_string(),
+            explanation: ctx.explain(access.variable),
+            bound_via: ctx.bound_via(access.variable).map(str::to_string),
+            ..Default::default()
+        });
+    }
+}
+
+/// Reports `reserved-name` for schema columns that shadow a pandas/polars method.
+pub struct ReservedNameRule;
+
+impl Rule for ReservedNameRule {
+    fn name(&self) -> &str {
+        "reserved-method"
+    }
+
+    fn on_schema_collected(
+        &self,
+        _ctx: &RuleContext,
+        schema: &SchemaCollected,
+        sink: &mut DiagnosticSink,
+    ) {
+        for col_name in schema.columns {
+            if RESERVED_METHODS.contains(&col_name.as_str()) {
+                sink.error(
+                    schema.line,
+                    schema.col,
+                    CODE_RESERVED_NAME,
+                    format!(
+                        "Column name {} in {} conflicts with a pandas/polars method. This will shadow the method when accessed via attribute syntax (df.{}). Consider renaming to {} or similar.",
+                        quote_column(col_name),
+                        schema.name,
+                        col_name,
+                        quote_column(&format!("{col_name}_value"))
+                    ),
+                );
+            }
+        }
+    }
+}
+
+/// Reports `schema-mismatch` when an annotated assignment declares one schema but its
+/// value is known to carry another, e.g. `df: DataFrame[UserSchema] = load_orders()`
+/// with `load_orders() -> DataFrame[OrderSchema]`, and when a function annotated
+/// `-> DataFrame[OrderSchema]` returns a variable tracked as another schema.  A value
+/// that names its own schema, `from_schema(raw, OrderSchema)` or
+/// `cast(DataFrame[OrderSchema], raw)`, conflicts the same way; the annotation is what
+/// gets tracked.  Only named, registered schemas are compared; inferred column sets and
+/// unknown values never fire.
+pub struct SchemaMismatchRule;
+
+impl SchemaMismatchRule {
+    fn value_schema(ctx: &RuleContext, value: &Expr) -> Option<String> {
+        match value {
+            Expr::Name(n) => ctx
+                .variable_schema(n.id.as_str())
+                .map(|(s, _)| s.to_string()),
+            Expr::Call(call) => match &*call.func {
+                Expr::Name(f) => ctx.function_schema(f.id.as_str()).map(str::to_string),
+                // DataFrame[Schema](...) / PandasFrame[Schema](...)
+                Expr::Subscript(_) => ctx.annotation_schema(&call.func),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+}
+
+impl Rule for SchemaMismatchRule {
+    fn name(&self) -> &str {
+        "schema-mismatch"
+    }
+
+    fn on_stmt_visited(&self, ctx: &RuleContext, stmt: &Stmt, sink: &mut DiagnosticSink) {
+        let confident = |s: &str| !s.starts_with("__inferred_") && ctx.columns(s).is_some();
+        if let Stmt::Return(ret) = stmt {
+            let (Some(Expr::Name(name)), Some((declared, annotation)), Some(function)) = (
+                ret.value.as_deref(),
+                ctx.declared_return(),
+                ctx.enclosing_function(),
+            ) else {
+                return;
+            };
+            let Some((actual, _)) = ctx.variable_schema(name.id.as_str()) else {
+                return;
+            };
+            if declared == actual || !confident(declared) || !confident(actual) {
+                return;
+            }
+            let (line, col) = ctx.location(ret);
+            sink.error(
+                line,
+                col,
+                CODE_SCHEMA_MISMATCH,
+                format!(
+                    "Function '{function}' declares {annotation} but returns a frame typed as \
+                     {actual}"
+                ),
+            );
+            return;
+        }
+        let Stmt::AnnAssign(ann_assign) = stmt else {
+            return;
+        };
+        let Some(value) = &ann_assign.value else {
+            return;
+        };
+        let Some(declared) = ctx.annotation_schema(&ann_assign.annotation) else {
+            return;
+        };
+        let stated = ctx.stated_schema(value);
+        let Some(actual) = stated
+            .as_ref()
+            .map(|(schema, _)| schema.clone())
+            .or_else(|| Self::value_schema(ctx, value))
+        else {
+            return;
+        };
+        if declared == actual || !confident(&declared) || !confident(&actual) {
+            return;
+        }
+        let (line, col) = ctx.location(ann_assign);
+        let message = match stated {
+            Some((_, via)) => format!(
+                "Schema mismatch: declared {declared} but {via} names {actual}; \
+                 {declared} from the annotation is tracked"
+            ),
+            None => format!("Schema mismatch: declared {declared} but value is {actual}"),
+        };
+        sink.error(line, col, CODE_SCHEMA_MISMATCH, message);
+    }
+}
+
+/// Warns with `conditional-column` when a column is read after an `if` that adds it by
+/// mutation in only some branches, e.g. `if cond: df["flag"] = 1` followed by
+/// `df["flag"]`.
+pub struct ConditionalColumnRule;
+
+impl Rule for ConditionalColumnRule {
+    fn name(&self) -> &str {
+        "conditional-column"
+    }
+
+    fn on_column_accessed(
+        &self,
+        ctx: &RuleContext,
+        access: &ColumnAccess,
+        sink: &mut DiagnosticSink,
+    ) {
+        let Some(if_line) = ctx.conditional_column(access.schema, access.column) else {
+            return;
+        };
+        sink.warning(
+            access.line,
+            access.col,
+            CODE_CONDITIONAL_COLUMN,
+            format!(
+                "Column {} may be missing from {}: it is only added under the condition at line {}",
+                quote_column(access.column),
+                ctx.describe_schema(access.schema, access.defined_line),
+                if_line
+            ),
+        );
+    }
+}
+
+/// Opt-in: warns with `mask-precedence` when a row filter combines masks in a way that
+/// only makes sense if the author forgot that `&` and `|` bind tighter than comparisons,
+/// e.g. `df[df["a"] > 1 & df["b"] < 2]`, which Python reads as
+/// `df["a"] > (1 & df["b"]) < 2`.  Two shapes are reported: a chained comparison with a
+/// bitwise operand, and a bitwise operation pairing a comparison with a raw column or
+/// integer.  Parenthesized comparisons and bitwise arithmetic on integer columns, such
+/// as `(df["flags"] & 4) > 0`, do not fire.
+pub struct MaskPrecedenceRule;
+
+impl MaskPrecedenceRule {
+    fn bitwise(expr: &Expr) -> Option<&ast::ExprBinOp> {
+        match expr {
+            Expr::BinOp(binop)
+                if matches!(binop.op, ast::Operator::BitAnd | ast::Operator::BitOr) =>
+            {
+                Some(binop)
+            }
+            _ => None,
+        }
+    }
+
+    // A column read straight off a tracked frame, or an integer literal.
+    fn is_raw_operand(ctx: &RuleContext, expr: &Expr) -> bool {
+        let tracked = |value: &Expr| matches!(value, Expr::Name(n) if ctx.variable_schema(n.id.as_str()).is_some());
+        match expr {
+            Expr::NumberLiteral(n) => matches!(n.value, ast::Number::Int(_)),
+            Expr::Subscript(sub) => {
+                tracked(&sub.value) && matches!(&*sub.slice, Expr::StringLiteral(_))
+            }
+            Expr::Attribute(attr) => {
+                tracked(&attr.value) && !RESERVED_METHODS.contains(&attr.attr.as_str())
+            }
+            _ => false,
+        }
+    }
+
+    // The first bitwise operation in `expr` that looks like a precedence mistake.
+    fn find<'e>(ctx: &RuleContext, expr: &'e Expr) -> Option<&'e ast::ExprBinOp> {
+        match expr {
+            Expr::BinOp(binop) if Self::bitwise(expr).is_some() => {
+                let (left, right) = (&*binop.left, &*binop.right);
+                let compare_with_raw = |a: &Expr, b: &Expr| {
+                    matches!(a, Expr::Compare(_)) && Self::is_raw_operand(ctx, b)
+                };
+                if compare_with_raw(left, right) || compare_with_raw(right, left) {
+                    return Some(binop);
+                }
+                Self::find(ctx, left).or_else(|| Self::find(ctx, right))
+            }
+            Expr::Compare(compare) => {
+                let operands = || std::iter::once(&*compare.left).chain(compare.comparators.iter());
+                if compare.ops.len() > 1 {
+                    if let Some(binop) = operands().find_map(Self::bitwise) {
+                        return Some(binop);
+                    }
+                }
+                operands().find_map(|operand| Self::find(ctx, operand))
+            }
+            Expr::UnaryOp(unary) => Self::find(ctx, &unary.operand),
+            _ => None,
+        }
+    }
+}
+
+impl Rule for MaskPrecedenceRule {
+    fn name(&self) -> &str {
+        "mask-precedence"
+    }
+
+    fn on_row_filter(&self, ctx: &RuleContext, filter: &RowFilter, sink: &mut DiagnosticSink) {
+        let Some(binop) = Self::find(ctx, filter.condition) else {
+            return;
+        };
+        let op = if matches!(binop.op, ast::Operator::BitAnd) {
+            "&"
+        } else {
+            "|"
+        };
+        let (line, col) = ctx.location(binop);
+        sink.warning(
+            line,
+            col,
+            CODE_MASK_PRECEDENCE,
+            format!(
+                "Likely precedence mistake in the row filter on '{}': `{op}` binds tighter than \
+                 comparisons, so this is not a combination of two masks; parenthesize each \
+                 comparison, e.g. `(df[\"a\"] > 1) {op} (df[\"b\"] < 2)`",
+                filter.variable
+            ),
+        );
+    }
+}
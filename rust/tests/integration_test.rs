@@ -1,4 +1,7 @@
-use _rust_checker::Linter;
+use _rust_checker::{
+    check_path, check_source, DiagnosticSink, Linter, Rule, RuleContext, SchemaCollected,
+};
+use ruff_python_ast::{Expr, Stmt};
 use std::fs;
 use tempfile::tempdir;
 
@@ -479,3 +482,191 @@ def main():
     // assert — created_at was inserted; access should be valid
     assert!(errors.is_empty(), "unexpected errors: {:?}", errors);
 }
+
+// Example organisation rule: every schema must declare a `load_ts` column.
+struct RequireLoadTs;
+
+impl Rule for RequireLoadTs {
+    fn name(&self) -> &str {
+        "require-load-ts"
+    }
+
+    fn on_schema_collected(
+        &self,
+        _ctx: &RuleContext,
+        schema: &SchemaCollected,
+        sink: &mut DiagnosticSink,
+    ) {
+        if !schema.columns.iter().any(|c| c == "load_ts") {
+            sink.error(
+                schema.line,
+                schema.col,
+                "require-load-ts",
+                format!("{} does not declare a 'load_ts' column", schema.name),
+            );
+        }
+    }
+}
+
+// Example organisation rule: tracked frames must not be passed to `print`.
+struct NoPrintFrame;
+
+impl Rule for NoPrintFrame {
+    fn name(&self) -> &str {
+        "no-print-frame"
+    }
+
+    fn on_stmt_visited(&self, ctx: &RuleContext, stmt: &Stmt, sink: &mut DiagnosticSink) {
+        let Stmt::Expr(expr_stmt) = stmt else {
+            return;
+        };
+        let Expr::Call(call) = &*expr_stmt.value else {
+            return;
+        };
+        if !matches!(&*call.func, Expr::Name(n) if n.id.as_str() == "print") {
+            return;
+        }
+        for arg in call.arguments.args.iter() {
+            if let Expr::Name(n) = arg {
+                if ctx.variable_schema(n.id.as_str()).is_some() {
+                    let (line, col) = ctx.location(call);
+                    sink.warning(
+                        line,
+                        col,
+                        "no-print-frame",
+                        format!("'{}' is a tracked frame passed to print()", n.id),
+                    );
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn test_should_run_custom_schema_rule_registered_via_builder() {
+    // arrange
+    let mut linter = Linter::builder().rule(Box::new(RequireLoadTs)).build();
+    let source = r#"
+from typedframes import BaseSchema, Column
+
+class UserSchema(BaseSchema):
+    user_id = Column(type=int)
+    load_ts = Column(type=str)
+
+class OrderSchema(BaseSchema):
+    order_id = Column(type=int)
+
+def main():
+    df: DataFrame[UserSchema] = load()
+    print(df["emial"])
+"#;
+    let dir = tempdir().unwrap();
+    let file_path = dir.path().join("test_rules.py");
+    fs::write(&file_path, source).unwrap();
+
+    // act
+    let errors = linter.check_file_internal(source, &file_path).unwrap();
+
+    // assert — custom rule fires only for OrderSchema; built-in rules still run
+    let custom: Vec<_> = errors
+        .iter()
+        .filter(|e| e.code == "require-load-ts")
+        .collect();
+    assert_eq!(custom.len(), 1, "errors: {:?}", errors);
+    assert!(custom[0].message.contains("OrderSchema"));
+    assert_eq!(custom[0].line, 8);
+    assert!(errors.iter().any(|e| e.code == "unknown-column"));
+}
+
+#[test]
+fn test_should_run_custom_stmt_rule_added_to_linter() {
+    // arrange
+    let mut linter = Linter::new();
+    linter.add_rule(Box::new(NoPrintFrame));
+    let source = r#"
+from typedframes import BaseSchema, Column
+
+class UserSchema(BaseSchema):
+    user_id = Column(type=int)
+
+def main():
+    df: DataFrame[UserSchema] = load()
+    other = 1
+    print(other)
+    print(df)
+"#;
+    let dir = tempdir().unwrap();
+    let file_path = dir.path().join("test_rules.py");
+    fs::write(&file_path, source).unwrap();
+
+    // act
+    let errors = linter.check_file_internal(source, &file_path).unwrap();
+
+    // assert
+    assert_eq!(errors.len(), 1, "errors: {:?}", errors);
+    assert_eq!(errors[0].code, "no-print-frame");
+    assert_eq!(errors[0].line, 11);
+    assert_eq!(errors[0].severity, "warning");
+}
+
+#[test]
+fn test_should_skip_builtin_checks_without_default_rules() {
+    // arrange
+    let mut linter = Linter::builder().without_default_rules().build();
+    let source = r#"
+from typedframes import BaseSchema, Column
+
+class UserSchema(BaseSchema):
+    user_id = Column(type=int)
+    shape = Column(type=int)
+
+def main():
+    df: DataFrame[UserSchema] = load()
+    print(df["emial"])
+"#;
+    let dir = tempdir().unwrap();
+    let file_path = dir.path().join("test_rules.py");
+    fs::write(&file_path, source).unwrap();
+
+    // act
+    let errors = linter.check_file_internal(source, &file_path).unwrap();
+
+    // assert
+    assert!(errors.is_empty(), "unexpected errors: {:?}", errors);
+}
+
+#[test]
+fn test_should_check_source_and_path_through_library_api() {
+    // arrange
+    let source = r#"
+from typedframes import BaseSchema, Column
+
+class UserSchema(BaseSchema):
+    user_id = Column(type=int)
+
+def main():
+    df: DataFrame[UserSchema] = load()
+    print(df["usr_id"])
+"#;
+    let dir = tempdir().unwrap();
+    let file_path = dir.path().join("app.py");
+    fs::write(&file_path, source).unwrap();
+    let disabled_dir = tempdir().unwrap();
+    fs::write(
+        disabled_dir.path().join("pyproject.toml"),
+        "[tool.typedframes]\nenabled = false\n",
+    )
+    .unwrap();
+    let disabled_path = disabled_dir.path().join("app.py");
+    fs::write(&disabled_path, source).unwrap();
+
+    // act
+    let from_source = check_source(source, &file_path).unwrap();
+    let from_path = check_path(&file_path).unwrap();
+    let from_disabled = check_path(&disabled_path).unwrap();
+
+    // assert
+    assert_eq!(from_source.len(), 1);
+    assert_eq!(from_source, from_path);
+    assert!(from_disabled.is_empty());
+}
@@ -3,10 +3,17 @@
 from __future__ import annotations
 
 import argparse
+import importlib
 import json
 import sys
 import time
-from pathlib import Path
+from collections import Counter
+from importlib import resources
+from pathlib import Path, PurePath
+from typing import TYPE_CHECKING
+
+if TYPE_CHECKING:
+    from types import ModuleType
 
 # ANSI escape sequences
 _RESET = "\033[0m"
@@ -14,21 +21,26 @@ _BOLD = "\033[1m"
 _BOLD_RED = "\033[1;31m"
 _BOLD_GREEN = "\033[1;32m"
 _BOLD_YELLOW = "\033[1;33m"
+_BOLD_CYAN = "\033[1;36m"
+_SEVERITY_COLORS = {"error": _BOLD_RED, "warning": _BOLD_YELLOW, "info": _BOLD_CYAN}
+# GitHub Actions has no "info" workflow command; its closest equivalent is "notice".
+_GITHUB_COMMANDS = {"info": "notice"}
+# Workflow-command escapes: message data, then the stricter set for file/title properties.
+_GITHUB_DATA_ESCAPES = str.maketrans({"%": "%25", "\r": "%0D", "\n": "%0A"})
+_GITHUB_PROPERTY_ESCAPES = str.maketrans({"%": "%25", "\r": "%0D", "\n": "%0A", ":": "%3A", ",": "%2C"})
+# Line breaks would split one finding across several text-output lines.
+_TEXT_ESCAPES = str.maketrans({"\r": "\\r", "\n": "\\n"})
 
 
-def _collect_python_files(path: Path) -> list[Path]:
-    """Collect all .py files from a path (file or directory)."""
-    if path.is_file():
-        if path.suffix == ".py":
-            return [path]
-        return []
-    return sorted(path.rglob("*.py"))
+def _display_path(path: PurePath) -> str:
+    """Return a path as every output format prints it: with forward slashes on every platform."""
+    return path.as_posix()
 
 
-def _check_files(files: list[Path], *, index_bytes: bytes | None = None) -> list[dict]:
-    """Run the Rust checker on each file, returning all errors with file paths."""
+def _load_checker() -> ModuleType:
+    """Return the Rust checker extension, exiting with an install hint when it is missing."""
     try:
-        from typedframes._rust_checker import check_file  # ty: ignore[unresolved-import]
+        checker = importlib.import_module("typedframes._rust_checker")
     except ImportError:
         msg = (
             "The Rust checker extension was not found. "
@@ -36,17 +48,124 @@ def _check_files(files: list[Path], *, index_bytes: bytes | None = None) -> list
         )
         print(msg, file=sys.stderr)
         sys.exit(1)
+    return checker
+
+
+def _collect_python_files(path: Path) -> list[Path]:
+    """Collect all .py files from a path (file or directory)."""
+    if path.is_file():
+        if path.suffix == ".py":
+            return [path]
+        return []
+    return sorted(path.rglob("*.py"))
+
+
+def _check_files(
+    files: list[Path],
+    *,
+    index_bytes: bytes | None = None,
+    explain_findings: bool | None = None,
+    config_path: Path | None = None,
+    strict: bool | None = None,
+    timings: list[dict] | None = None,
+) -> list[dict]:
+    """Run the Rust checker on each file, returning all errors with file paths.
+
+    When ``timings`` is a list, one entry per file is appended with its size, total
+    wall-clock seconds and the checker's parse/lint/serialize breakdown.
+    """
+    check_file = _load_checker().check_file
 
     all_errors = []
     for file_path in files:
-        result_json = check_file(str(file_path), index_bytes)
+        phases: dict[str, float] | None = {} if timings is not None else None
+        start = time.perf_counter()
+        try:
+            result_json = check_file(
+                str(file_path),
+                index_bytes,
+                explain_findings,
+                str(config_path) if config_path else None,
+                strict,
+                phases,
+            )
+        except ValueError as exc:
+            print(f"Error: {exc}", file=sys.stderr)
+            sys.exit(2)
         errors = json.loads(result_json)
+        if timings is not None:
+            timings.append(
+                {
+                    "file": _display_path(file_path),
+                    "bytes": file_path.stat().st_size,
+                    "total": time.perf_counter() - start,
+                    **dict.fromkeys(_PHASES, 0.0),
+                    **(phases or {}),
+                }
+            )
         for error in errors:
-            error["file"] = str(file_path)
+            error["file"] = _display_path(file_path)
         all_errors.extend(errors)
     return all_errors
 
 
+_SEVERITY_RANK = {"info": 0, "warning": 1, "error": 2}
+
+
+def _filter_diagnostics(
+    errors: list[dict], *, strict_ingest: bool = False, no_warnings: bool = False, min_severity: str = "info"
+) -> list[dict]:
+    """Apply the output filters shared by every format: ingestion warnings, --no-warnings and --min-severity."""
+    if not strict_ingest:
+        errors = [e for e in errors if e.get("code") != "untracked-dataframe"]
+    if no_warnings:
+        errors = [e for e in errors if e.get("severity") != "warning"]
+    threshold = _SEVERITY_RANK[min_severity]
+    return [e for e in errors if _SEVERITY_RANK.get(e.get("severity", "error"), 0) >= threshold]
+
+
+def _summarize(errors: list[dict], file_count: int, *, strict: bool) -> dict:
+    """Count diagnostics per severity and per code, and decide the exit code."""
+    by_severity = Counter(e.get("severity", "error") for e in errors)
+    by_code = Counter(e.get("code", "") for e in errors)
+    return {
+        "files": file_count,
+        "by_severity": dict(sorted(by_severity.items())),
+        "by_code": dict(sorted(by_code.items())),
+        "exit_code": 1 if strict and by_severity["error"] else 0,
+    }
+
+
+def _format_summary(summary: dict) -> str:
+    """Format a --summary-only summary as a text table."""
+    file_label = "file" if summary["files"] == 1 else "files"
+    lines = [f"Checked {summary['files']} {file_label}"]
+    lines.extend(f"  {severity:<8} {count}" for severity, count in summary["by_severity"].items())
+    if summary["by_code"]:
+        lines.append("By code:")
+        width = max(len(code) for code in summary["by_code"])
+        lines.extend(f"  {code:<{width}} {count}" for code, count in summary["by_code"].items())
+    lines.append(f"Exit code: {summary['exit_code']}")
+    return "\n".join(lines)
+
+
+_PHASES = ("parse", "lint", "serialize")
+_STATS_TOP_N = 5
+
+
+def _format_stats(timings: list[dict], elapsed: float, *, top: int = _STATS_TOP_N) -> str:
+    """Format the --stats summary: total time and the slowest files with their phase breakdown."""
+    file_label = "file" if len(timings) == 1 else "files"
+    lines = [f"Stats: checked {len(timings)} {file_label} in {elapsed:.3f}s"]
+    slowest = sorted(timings, key=lambda t: t["total"], reverse=True)[:top]
+    if slowest:
+        lines.append(f"Slowest {len(slowest)}:")
+    for timing in slowest:
+        phases = ", ".join(f"{phase} {timing[phase]:.3f}s" for phase in _PHASES)
+        lines.append(f"  {timing['total']:.3f}s  {timing['file']} ({timing['bytes']} bytes; {phases})")
+    return "\n".join(lines)
+
+
 def _format_text(errors: list[dict], *, color: bool = False) -> str:
     """Format errors as text lines using ty-style file:line:col: severity[code] message."""
     lines = []
@@ -56,34 +175,56 @@ def _format_text(errors: list[dict], *, color: bool = False) -> str:
         file_ = error["file"]
         line = error["line"]
         col = error["col"]
-        message = error["message"]
+        message = error["message"].translate(_TEXT_ESCAPES)
         code_part = f"[{code}]" if code else ""
         if color:
-            sev_colored = f"{_BOLD_RED}error{_RESET}" if severity == "error" else f"{_BOLD_YELLOW}warning{_RESET}"
+            sev_colored = f"{_SEVERITY_COLORS.get(severity, _BOLD_RED)}{severity}{_RESET}"
             lines.append(f"{_BOLD}{file_}{_RESET}:{line}:{col}: {sev_colored}{code_part} {message}")
         else:
             lines.append(f"{file_}:{line}:{col}: {severity}{code_part} {message}")
+        explanation = error.get("explanation")
+        if explanation:
+            lines.extend(_format_explanation(explanation))
     return "\n".join(lines)
 
 
+def _format_explanation(explanation: dict) -> list[str]:
+    """Format an --explain-findings evidence trail as indented note lines."""
+    columns = ", ".join(explanation["columns"])
+    if explanation["total_columns"] > len(explanation["columns"]):
+        columns += f", ... ({explanation['total_columns']} total)"
+    lines = [
+        f"  = note: '{explanation['variable']}' bound via {explanation['bound_via']} at line {explanation['bound_at']}",
+        f"  = note: known columns: {{{columns}}}",
+    ]
+    lines.extend(
+        f"  = note: line {step['line']}: {step['kind']} {step['detail']}" for step in explanation["transforms"]
+    )
+    return lines
+
+
 def _format_github(errors: list[dict]) -> str:
     """Format errors as GitHub Actions workflow commands."""
     lines = []
     for error in errors:
         severity = error.get("severity", "error")
         code = error.get("code", "")
-        file_ = error["file"]
+        file_ = error["file"].translate(_GITHUB_PROPERTY_ESCAPES)
         line = error["line"]
         col = error["col"]
-        message = error["message"]
-        title = code or severity
-        lines.append(f"::{severity} file={file_},line={line},col={col},title={title}::{message}")
+        message = error["message"].translate(_GITHUB_DATA_ESCAPES)
+        title = (code or severity).translate(_GITHUB_PROPERTY_ESCAPES)
+        command = _GITHUB_COMMANDS.get(severity, severity)
+        lines.append(f"::{command} file={file_},line={line},col={col},title={title}::{message}")
     return "\n".join(lines)
 
 
 def main(argv: list[str] | None = None) -> None:
     """Entry point for the typedframes CLI."""
     parser = argparse.ArgumentParser(prog="typedframes", description="Static analysis for DataFrame column schemas.")
+    parser.add_argument(
+        "--output-schema", action="store_true", help="Print the JSON Schema of the json output format and exit."
+    )
     subparsers = parser.add_subparsers(dest="command")
 
     check_parser = subparsers.add_parser("check", help="Check Python files for column errors.")
@@ -105,19 +246,120 @@ def main(argv: list[str] | None = None) -> None:
         help=argparse.SUPPRESS,
     )
     check_parser.add_argument("--no-index", action="store_true", help="Disable cross-file index.")
+    check_parser.add_argument(
+        "--config",
+        type=Path,
+        default=None,
+        metavar="PATH",
+        help="Read [tool.typedframes] from this file instead of the discovered pyproject.toml.",
+    )
     check_parser.add_argument(
         "--no-warnings",
         action="store_true",
         help="Suppress all warnings (dropped-unknown-column and any enabled ingestion warnings).",
     )
+    check_parser.add_argument(
+        "--explain-findings",
+        action="store_true",
+        help="Attach the evidence behind each finding: how the variable was bound and the transforms applied.",
+    )
+    check_parser.add_argument(
+        "--min-severity",
+        choices=list(_SEVERITY_RANK),
+        default="info",
+        dest="min_severity",
+        help="Drop diagnostics below this severity before output (default: info, i.e. keep everything).",
+    )
+    check_parser.add_argument(
+        "--summary-only",
+        action="store_true",
+        help="Print only per-severity and per-code counts and the exit code instead of each diagnostic.",
+    )
+    check_parser.add_argument(
+        "--stats",
+        action="store_true",
+        help=f"Print total time and the {_STATS_TOP_N} slowest files after the summary.",
+    )
+    check_parser.add_argument(
+        "--profile",
+        type=Path,
+        default=None,
+        metavar="PATH",
+        help="Write a per-file parse/lint/serialize timing breakdown to this JSON file.",
+    )
     check_parser.add_argument(
         "--strict-ingest",
         action="store_true",
         help="Include untracked-dataframe warnings for bare DataFrame loads without usecols= or columns=.",
     )
+    manifest_group = check_parser.add_mutually_exclusive_group()
+    manifest_group.add_argument(
+        "--write-manifest",
+        type=Path,
+        default=None,
+        metavar="PATH",
+        help="Write a run manifest (versions, settings, file hashes, counts per code) to this JSON file.",
+    )
+    manifest_group.add_argument(
+        "--verify-manifest",
+        type=Path,
+        default=None,
+        metavar="PATH",
+        help="Compare this run against a manifest from --write-manifest; exit 1 if they diverge.",
+    )
+    check_parser.add_argument(
+        "--show-settings",
+        action="store_true",
+        help="Print the effective configuration for PATH and where each value came from, then exit.",
+    )
+
+    config_parser = subparsers.add_parser(
+        "config-check", help="Validate the [tool.typedframes] configuration of a project."
+    )
+    config_parser.add_argument(
+        "path", type=Path, nargs="?", default=Path(), help="Project root containing pyproject.toml (default: .)."
+    )
+    config_parser.add_argument(
+        "--config",
+        type=Path,
+        default=None,
+        metavar="PATH",
+        help="Validate this file instead of the project's pyproject.toml.",
+    )
+    config_parser.add_argument(
+        "--output-format",
+        choices=["text", "json", "github"],
+        default="text",
+        dest="output_format",
+        help="Output format: text (default), json, or github (GitHub Actions annotations).",
+    )
+
+    annotate_parser = subparsers.add_parser(
+        "annotate", help="Propose schema annotations for unannotated DataFrame loads."
+    )
+    annotate_parser.add_argument("path", type=Path, help="File or directory to annotate.")
+    annotate_parser.add_argument(
+        "--schema-match",
+        action="store_true",
+        required=True,
+        help="Annotate each read_csv/read_parquet/... load whose column reads fit exactly one known schema.",
+    )
+    annotate_parser.add_argument(
+        "--apply", action="store_true", help="Write the proposed annotations into the files instead of listing them."
+    )
+    annotate_parser.add_argument("--no-index", action="store_true", help="Disable cross-file index.")
 
     args = parser.parse_args(argv)
 
+    if args.output_schema:
+        print(_output_schema())
+        return
+    if args.command == "config-check":
+        _run_config_check(args)
+        return
+    if args.command == "annotate":
+        _run_annotate(args)
+        return
     if args.command != "check":
         parser.print_help()
         sys.exit(2)
@@ -125,9 +367,144 @@ def main(argv: list[str] | None = None) -> None:
     _run_check(args)
 
 
+def _output_schema() -> str:
+    """Return the JSON Schema document describing ``--output-format json`` diagnostics."""
+    return resources.files("typedframes").joinpath("lint_output.schema.json").read_text()
+
+
+def _validate_config(config_path: Path) -> list[dict]:
+    """Run the Rust config validator on ``config_path``, returning its findings."""
+    return json.loads(_load_checker().validate_config(str(config_path.parent), str(config_path)))
+
+
+def _resolve_settings(
+    file_path: Path,
+    *,
+    config_path: Path | None,
+    explain_findings: bool | None,
+    strict: bool | None,
+) -> dict:
+    """Resolve the effective settings for ``file_path`` with the Rust checker, tagging each value's source."""
+    try:
+        report = _load_checker().resolve_settings(
+            str(file_path),
+            str(config_path) if config_path is not None else None,
+            explain_findings,
+            strict,
+        )
+    except (ValueError, FileNotFoundError) as exc:
+        print(f"Error: {exc}", file=sys.stderr)
+        sys.exit(2)
+    return json.loads(report)
+
+
+def _format_settings(report: dict) -> str:
+    """Format a ``resolve_settings`` report as one ``key = value  (source)`` line per setting."""
+    lines = [f"Settings for {report['file']}", f"Config file: {report['config_file']}"]
+    width = max((len(key) for key in report["settings"]), default=0)
+    for key, setting in report["settings"].items():
+        value = json.dumps(setting["value"])
+        lines.append(f"  {key:<{width}} = {value}  ({setting['source']})")
+    return "\n".join(lines)
+
+
+def _run_config_check(args: argparse.Namespace) -> None:
+    """Execute the config-check subcommand; exits 1 if the configuration has errors."""
+    config_path: Path = (args.config or args.path / "pyproject.toml").resolve()
+    if not config_path.is_file():
+        print(f"Error: config file does not exist: {config_path}", file=sys.stderr)
+        sys.exit(2)
+
+    findings = _validate_config(config_path)
+    for finding in findings:
+        finding["file"] = str(config_path)
+
+    if args.output_format == "json":
+        print(json.dumps(findings, indent=2))
+    elif args.output_format == "github":
+        if findings:
+            print(_format_github(findings))
+    else:
+        use_color = hasattr(sys.stdout, "isatty") and sys.stdout.isatty()
+        if findings:
+            print(_format_text(findings, color=use_color))
+            print()
+            label = "problem" if len(findings) == 1 else "problems"
+            msg = f"\u2717 Found {len(findings)} configuration {label} in {config_path}"
+            print(f"{_BOLD_RED}{msg}{_RESET}" if use_color else msg)
+        else:
+            msg = f"\u2713 Configuration in {config_path} is valid"
+            print(f"{_BOLD_GREEN}{msg}{_RESET}" if use_color else msg)
+
+    if any(f.get("severity", "error") == "error" for f in findings):
+        sys.exit(1)
+
+
+def _run_annotate(args: argparse.Namespace) -> None:
+    """Execute the annotate subcommand: list the proposed annotations, or write them with --apply."""
+    path: Path = args.path.resolve()
+    if not path.exists():
+        print(f"Error: path does not exist: {args.path}", file=sys.stderr)
+        sys.exit(2)
+    checker = _load_checker()
+    index_bytes = checker.build_project_index(str(path)) if path.is_dir() and not args.no_index else None
+
+    counts = Counter()
+    for file_path in _collect_python_files(path):
+        suggestions = json.loads(checker.suggest_annotations(str(file_path), index_bytes))
+        for suggestion in suggestions:
+            print(_format_suggestion(file_path, suggestion))
+            if suggestion["annotation"]:
+                counts["annotated"] += 1
+            elif suggestion["schemas"]:
+                counts["ambiguous"] += 1
+            else:
+                counts["unmatched"] += 1
+        edits = [s for s in suggestions if s["annotation"]]
+        if args.apply and edits:
+            _apply_annotations(file_path, edits)
+
+    verb = "applied" if args.apply else "proposed"
+    print(
+        f"{counts['annotated']} annotation(s) {verb}, "
+        f"{counts['ambiguous']} ambiguous, {counts['unmatched']} unmatched"
+    )
+    if args.apply and counts["annotated"]:
+        print("Import the frame types and schemas the new annotations name where they are not already in scope.")
+
+
+def _format_suggestion(file_path: Path, suggestion: dict) -> str:
+    """Format one ``suggest_annotations`` entry as a ``file:line:col: ...`` line."""
+    where = f"{_display_path(file_path)}:{suggestion['line']}:{suggestion['col']}"
+    variable = suggestion["variable"]
+    columns = ", ".join(suggestion["columns"])
+    if suggestion["annotation"]:
+        return f"{where}: {variable}{suggestion['annotation']}"
+    if not suggestion["columns"]:
+        return f"{where}: '{variable}' not annotated: no columns are read from it"
+    if suggestion["schemas"]:
+        schemas = ", ".join(suggestion["schemas"])
+        return f"{where}: '{variable}' not annotated: columns {{{columns}}} fit several schemas ({schemas})"
+    return f"{where}: '{variable}' not annotated: no known schema has columns {{{columns}}}"
+
+
+def _apply_annotations(file_path: Path, edits: list[dict]) -> None:
+    """Insert each edit's annotation at its 1-indexed line and column; exits 2 if the file cannot be written."""
+    lines = file_path.read_text().splitlines(keepends=True)
+    for edit in sorted(edits, key=lambda e: (e["line"], e["col"]), reverse=True):
+        line = lines[edit["line"] - 1]
+        at = edit["col"] - 1
+        lines[edit["line"] - 1] = line[:at] + edit["annotation"] + line[at:]
+    try:
+        file_path.write_text("".join(lines))
+    except OSError as exc:
+        print(f"Error: cannot write {file_path}: {exc}", file=sys.stderr)
+        sys.exit(2)
+
+
 def _print_results(files: list[Path], all_errors: list[dict], elapsed: float, *, output_format: str) -> None:
     """Print check results in the requested format."""
-    errors_only = [e for e in all_errors if e.get("severity") != "warning"]
+    errors_only = [e for e in all_errors if e.get("severity", "error") == "error"]
     warnings = [e for e in all_errors if e.get("severity") == "warning"]
 
     if output_format == "json":
@@ -175,6 +552,28 @@ def _run_check(args: argparse.Namespace) -> None:
             print(f"Error: path does not exist: {original!r} (resolved to {path})", file=sys.stderr)
         sys.exit(2)
 
+    if args.show_settings:
+        report = _resolve_settings(
+            path,
+            config_path=args.config.resolve() if args.config is not None else None,
+            explain_findings=True if args.explain_findings else None,
+            strict=True if args.strict else None,
+        )
+        print(json.dumps(report, indent=2) if args.output_format == "json" else _format_settings(report))
+        return
+
+    config_path: Path | None = None
+    if args.config is not None:
+        config_path = args.config.resolve()
+        if not config_path.is_file():
+            print(f"Error: config file does not exist: {args.config}", file=sys.stderr)
+            sys.exit(2)
+
+    # `--strict`, else TYPEDFRAMES_STRICT and the other layers, as the checker resolves them.
+    strict_argument = True if args.strict else None
+    settings = _resolve_settings(path, config_path=config_path, explain_findings=None, strict=strict_argument)
+    strict: bool = settings["settings"]["strict"]["value"]
+
     index_bytes: bytes | None = None
     if path.is_dir() and not args.no_index:
         try:
@@ -185,18 +584,127 @@ def _run_check(args: argparse.Namespace) -> None:
             pass
 
     files = _collect_python_files(path)
+    timings: list[dict] | None = [] if args.stats or args.profile else None
     start = time.perf_counter()
-    all_errors = _check_files(files, index_bytes=index_bytes)
+    all_errors = _check_files(
+        files,
+        index_bytes=index_bytes,
+        explain_findings=True if args.explain_findings else None,
+        config_path=config_path,
+        strict=strict_argument,
+        timings=timings,
+    )
     elapsed = time.perf_counter() - start
 
-    if not args.strict_ingest:
-        all_errors = [e for e in all_errors if e.get("code") != "untracked-dataframe"]
+    all_errors = _filter_diagnostics(
+        all_errors,
+        strict_ingest=args.strict_ingest,
+        no_warnings=args.no_warnings,
+        min_severity=args.min_severity,
+    )
+    summary = _summarize(all_errors, len(files), strict=strict)
+
+    if not args.summary_only:
+        _print_results(files, all_errors, elapsed, output_format=args.output_format)
+    elif args.output_format == "json":
+        print(json.dumps(summary, indent=2))
+    else:
+        print(_format_summary(summary))
 
-    if args.no_warnings:
-        all_errors = [e for e in all_errors if e.get("severity") != "warning"]
+    if timings is not None:
+        _report_timings(timings, elapsed, stats=args.stats, profile=args.profile, output_format=args.output_format)
 
-    errors_only = [e for e in all_errors if e.get("severity") != "warning"]
-    _print_results(files, all_errors, elapsed, output_format=args.output_format)
+    if args.write_manifest is not None or args.verify_manifest is not None:
+        manifest = _run_manifest(path, summary["by_code"], config_path=config_path, strict=strict)
+        if args.write_manifest is not None:
+            _write_manifest(args.write_manifest, manifest)
+        else:
+            _verify_manifest(args.verify_manifest, manifest, output_format=args.output_format)
 
-    if args.strict and errors_only:
-        sys.exit(1)
+    if summary["exit_code"]:
+        sys.exit(summary["exit_code"])
+
+
+def _run_manifest(path: Path, by_code: dict, *, config_path: Path | None, strict: bool) -> dict:
+    """Build the run manifest for ``path`` with the Rust checker."""
+    try:
+        report = _load_checker().run_manifest(
+            str(path),
+            json.dumps(by_code),
+            str(config_path) if config_path is not None else None,
+            strict,
+        )
+    except (ValueError, FileNotFoundError) as exc:
+        print(f"Error: {exc}", file=sys.stderr)
+        sys.exit(2)
+    return json.loads(report)
+
+
+def _write_manifest(manifest_path: Path, manifest: dict) -> None:
+    """Write ``manifest`` as --write-manifest does; exits 2 if the file cannot be written."""
+    try:
+        manifest_path.write_text(json.dumps(manifest, indent=2) + "\n")
+    except OSError as exc:
+        print(f"Error: cannot write manifest {manifest_path}: {exc}", file=sys.stderr)
+        sys.exit(2)
+
+
+def _verify_manifest(manifest_path: Path, manifest: dict, *, output_format: str) -> None:
+    """Compare this run's ``manifest`` with the one at ``manifest_path``; exits 1 if they diverge."""
+    try:
+        expected = json.loads(manifest_path.read_text())
+    except (OSError, ValueError) as exc:
+        print(f"Error: cannot read manifest {manifest_path}: {exc}", file=sys.stderr)
+        sys.exit(2)
+    differences = _manifest_differences(expected, manifest)
+    # Keep machine-readable stdout (json, github) parseable.
+    stream = sys.stdout if output_format == "text" else sys.stderr
+    if not differences:
+        print(f"Run matches manifest {_display_path(manifest_path)}", file=stream)
+        return
+    print(f"Run diverges from manifest {_display_path(manifest_path)}:", file=stream)
+    for difference in differences:
+        print(f"  {difference}", file=stream)
+    sys.exit(1)
+
+
+def _manifest_differences(expected: dict, actual: dict) -> list[str]:
+    """Describe each way the ``actual`` run manifest diverges from the ``expected`` one."""
+    differences = []
+    for key, label in (("tool_version", "tool version"), ("parser", "parser")):
+        if expected.get(key) != actual.get(key):
+            differences.append(f"{label} changed: {expected.get(key)} -> {actual.get(key)}")
+    for kind, key, item in (("config", "settings", "setting"), ("file", "files", "file")):
+        old, new = expected.get(key, {}), actual.get(key, {})
+        for name in sorted(old.keys() | new.keys()):
+            if name not in new:
+                differences.append(f"{item} removed: {name}")
+            elif name not in old:
+                differences.append(f"{item} added: {name}")
+            elif old[name] != new[name]:
+                detail = f": {json.dumps(old[name])} -> {json.dumps(new[name])}" if kind == "config" else ""
+                differences.append(f"{item} changed: {name}{detail}")
+    if expected.get("schema_fingerprint") != actual.get("schema_fingerprint"):
+        differences.append("schema registry changed")
+    old_counts, new_counts = expected.get("counts", {}), actual.get("counts", {})
+    for code in sorted(old_counts.keys() | new_counts.keys()):
+        if old_counts.get(code, 0) != new_counts.get(code, 0):
+            differences.append(f"count changed: {code} {old_counts.get(code, 0)} -> {new_counts.get(code, 0)}")
+    return differences
+
+
+def _report_timings(
+    timings: list[dict], elapsed: float, *, stats: bool, profile: Path | None, output_format: str
+) -> None:
+    """Print the --stats summary and write the --profile JSON, as requested."""
+    if stats:
+        # Keep machine-readable stdout (json, github) parseable.
+        stream = sys.stdout if output_format == "text" else sys.stderr
+        print(_format_stats(timings, elapsed), file=stream)
+    if profile is not None:
+        report = {"total": elapsed, "files": timings}
+        try:
+            profile.write_text(json.dumps(report, indent=2) + "\n")
+        except OSError as exc:
+            print(f"Error: cannot write profile {profile}: {exc}", file=sys.stderr)
+            sys.exit(2)
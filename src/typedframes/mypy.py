@@ -3,6 +3,7 @@
 import json
 import tomllib
 from collections.abc import Callable
+from dataclasses import dataclass
 from pathlib import Path
 from typing import TYPE_CHECKING, Any
 
@@ -37,18 +38,105 @@ def is_enabled(project_root: Path) -> bool:
         return True
 
 
+def _fingerprint(path: Path) -> tuple[int, int] | None:
+    """Return the (mtime_ns, size) of a file, or None when it does not exist."""
+    try:
+        stat = path.stat()
+    except OSError:
+        return None
+    return (stat.st_mtime_ns, stat.st_size)
+
+
 class CheckerNotFoundError(Exception):
     """Raised when the typedframes checker cannot be found or executed."""
 
 
+# (schema name, defining file, hash of its columns), as reported by the checker.
+SchemaDependency = tuple[str, str, str]
+
+
+@dataclass
+class CachedResult:
+    """A file's checker diagnostics and the project schemas they were computed against."""
+
+    errors: list[dict[str, Any]]
+    dependencies: frozenset[SchemaDependency]
+
+
+@dataclass
+class CacheStats:
+    """How often cached results were reused, recomputed or dropped because a schema changed."""
+
+    hits: int = 0
+    misses: int = 0
+    invalidations: int = 0
+
+
+def _dependencies(entries: list[dict[str, str]]) -> frozenset[SchemaDependency]:
+    """Return the checker's ``{"schema", "file", "hash"}`` objects as dependency tuples."""
+    return frozenset((entry["schema"], entry["file"], entry["hash"]) for entry in entries)
+
+
 class TypedFramesPlugin(Plugin):
-    """Mypy plugin to integrate the typedframes Rust checker."""
+    """Mypy plugin to integrate the typedframes Rust checker.
+
+    Under the mypy daemon the plugin outlives a single run, so cached results and
+    project indexes are invalidated when their sources change: an edited
+    ``pyproject.toml`` reloads everything, and an edit to a file the plugin has already
+    checked refreshes that file's entries in the project index. Each cached result
+    records the project schemas it read, so only the results that read a schema whose
+    columns the edit changed are re-checked. ``cache_stats`` counts hits, misses and
+    those invalidations.
+    """
 
     def __init__(self, *args: Any, **kwargs: Any) -> None:
         """Initialize the plugin."""
         super().__init__(*args, **kwargs)
-        self._checker_results: dict[str, list[dict[str, Any]]] = {}
+        self._checker_results: dict[str, CachedResult] = {}
         self._index_bytes_by_root: dict[str, bytes | None] = {}
+        self._fingerprints: dict[str, tuple[int, int] | None] = {}
+        self.cache_stats = CacheStats()
+
+    def reload(self) -> None:
+        """Drop all cached results and project indexes, forcing a full reload on the next check."""
+        self._checker_results.clear()
+        self._index_bytes_by_root.clear()
+
+    def _has_changed(self, path: Path) -> bool:
+        """Record the file's fingerprint, returning whether it differs from the one last recorded."""
+        key = str(path)
+        current = _fingerprint(path)
+        changed = key in self._fingerprints and self._fingerprints[key] != current
+        self._fingerprints[key] = current
+        return changed
+
+    def _invalidate_source(self, file_path: str) -> None:
+        """Refresh the project index for an edited file and drop the cached results that read its schemas.
+
+        Without a project index there is nothing to compare against, so every cached
+        result in the project is dropped.
+        """
+        project_root = get_project_root(Path(file_path))
+        self._checker_results.pop(file_path, None)
+        in_project = [path for path in self._checker_results if Path(path).resolve().is_relative_to(project_root)]
+        index_bytes = self._index_bytes_by_root.get(str(project_root))
+        try:
+            from typedframes._rust_checker import (  # ty: ignore[unresolved-import]
+                schema_hashes,
+                update_project_index,
+            )
+        except ImportError:
+            index_bytes = None
+        if index_bytes is None:
+            stale = in_project
+        else:
+            index_bytes = update_project_index(index_bytes, str(project_root), str(Path(file_path).resolve()))
+            self._index_bytes_by_root[str(project_root)] = index_bytes
+            current = _dependencies(json.loads(schema_hashes(index_bytes)))
+            stale = [path for path in in_project if not self._checker_results[path].dependencies <= current]
+        for path in stale:
+            del self._checker_results[path]
+        self.cache_stats.invalidations += len(stale)
 
     def _get_index_bytes(self, project_root: Path) -> bytes | None:
         """Build and cache the project index as MessagePack bytes, keyed by project root."""
@@ -62,29 +150,36 @@ class TypedFramesPlugin(Plugin):
                 self._index_bytes_by_root[key] = None
         return self._index_bytes_by_root[key]
 
-    def _run_via_extension(self, file_path: str, index_bytes: bytes | None) -> list[dict[str, Any]]:
+    def _run_via_extension(self, file_path: str, index_bytes: bytes | None) -> CachedResult:
         """Run the checker via the Rust extension module."""
-        from typedframes._rust_checker import check_file  # ty: ignore[unresolved-import]
+        from typedframes._rust_checker import check_file_with_dependencies  # ty: ignore[unresolved-import]
 
-        result_json = str(check_file(file_path, index_bytes))
-        return json.loads(result_json)
+        result = json.loads(str(check_file_with_dependencies(file_path, index_bytes)))
+        return CachedResult(result["diagnostics"], _dependencies(result["dependencies"]))
 
     def _run_checker(self, file_path: str) -> list[dict[str, Any]]:
         """Run the Rust checker on the given file."""
-        if file_path in self._checker_results:
-            return self._checker_results[file_path]
-
         if not file_path or "site-packages" in file_path or file_path.endswith(".pyi"):
             return []
 
         project_root = get_project_root(Path(file_path))
+        if self._has_changed(project_root / "pyproject.toml"):
+            self.reload()
+
+        if file_path in self._checker_results:
+            if not self._has_changed(Path(file_path)):
+                self.cache_stats.hits += 1
+                return self._checker_results[file_path].errors
+            self._invalidate_source(file_path)
+        self.cache_stats.misses += 1
+
         if not is_enabled(project_root):
             return []
 
         index_bytes = self._get_index_bytes(project_root)
 
         try:
-            errors = self._run_via_extension(file_path, index_bytes)
+            result = self._run_via_extension(file_path, index_bytes)
         except ImportError as e:
             msg = (
                 "typedframes checker extension not found. "
@@ -93,8 +188,9 @@ class TypedFramesPlugin(Plugin):
             )
             raise CheckerNotFoundError(msg) from e
 
-        self._checker_results[file_path] = errors
-        return errors
+        self._has_changed(Path(file_path))
+        self._checker_results[file_path] = result
+        return result.errors
 
     def get_method_hook(
         self,
@@ -0,0 +1,32 @@
+"""Test fixture: analyze_file model across a merge, a drop and a reassignment."""
+
+from typing import Annotated
+
+import pandas as pd
+
+from typedframes import BaseSchema, Column
+
+
+class UserSchema(BaseSchema):
+    """Test schema for user data."""
+
+    user_id = Column(type=int)
+    email = Column(type=str)
+
+
+class OrderSchema(BaseSchema):
+    """Test schema for order data."""
+
+    order_id = Column(type=int)
+    user_id = Column(type=int)
+
+
+users: Annotated[pd.DataFrame, UserSchema] = pd.read_csv("users.csv")
+
+
+def build(orders: Annotated[pd.DataFrame, OrderSchema]) -> pd.DataFrame:
+    """Merge, drop and reselect columns."""
+    merged = users.merge(orders, on="user_id")
+    slim = merged.drop(columns=["email"])
+    slim = slim[["order_id"]]
+    return slim
@@ -0,0 +1,32 @@
+"""Test fixture: literals in data positions that look like column names but must not be reported."""
+
+from typing import Annotated
+
+import pandas as pd
+import polars as pl
+
+from typedframes import BaseSchema, Column
+
+
+class OrderSchema(BaseSchema):
+    """Test schema for order data."""
+
+    order_id = Column(type=int)
+    status = Column(type=str)
+
+
+orders: Annotated[pd.DataFrame, OrderSchema] = pd.read_csv("orders.csv")
+
+# Dict keys and list items are status values, not columns.
+codes = orders["status"].map({"statu": 1, "order_i": 2})
+renamed = orders["status"].replace({"statu": "status"})
+cleaned = orders.replace(to_replace=["order_i"], value="order_id")
+open_orders = orders[orders["status"].isin(["statu", "order_i"])]
+filled = orders.fillna({"statu": "unknown"})
+
+frame: Annotated[pl.DataFrame, OrderSchema] = pl.read_csv("orders.csv")
+
+polars_codes = frame["status"].replace({"statu": "status"})
+strict_codes = frame["status"].replace_strict(old=["order_i"], new=["order_id"])
+polars_open = frame.filter(pl.col("status").is_in(["statu", "order_i"]))
+polars_filled = frame["status"].fill_null("statu")
@@ -5,7 +5,15 @@ import tempfile
 import unittest
 from pathlib import Path
 
-from typedframes._rust_checker import build_project_index, check_file  # ty: ignore[unresolved-import]
+from typedframes._rust_checker import (  # ty: ignore[unresolved-import]
+    analyze_file,
+    build_project_index,
+    check_directory,
+    check_file,
+    check_source,
+    lint_many_sources,
+    lint_snippet,
+)
 
 
 class TestTypedFramesCheckerIntegration(unittest.TestCase):
@@ -824,3 +832,259 @@ def enrich(df: PandasFrame[Schema]):
             self.assertIn("'bad'", errors[0]["message"])
         finally:
             Path(temp_file).unlink()
+
+    def test_should_skip_file_when_explicit_config_disables_linter(self) -> None:
+        """Test that a config_path whose [tool.typedframes] disables the linter suppresses all errors."""
+        # arrange
+        source = """
+import pandas as pd
+
+df = pd.read_csv("users.csv", usecols=["user_id"])
+print(df["email"])
+"""
+        with tempfile.TemporaryDirectory() as tmpdir:
+            root = Path(tmpdir)
+            (root / "pyproject.toml").write_text("[tool.typedframes]\nenabled = true\n")
+            config_file = root / "ci.toml"
+            config_file.write_text("[tool.typedframes]\nenabled = false\n")
+            py_file = root / "pipeline.py"
+            py_file.write_text(source)
+
+            # act
+            result = check_file(str(py_file), None, None, str(config_file))
+
+            # assert
+            self.assertEqual(json.loads(result), [])
+
+    def test_should_check_file_when_explicit_config_enables_linter(self) -> None:
+        """Test that a config_path enabling the linter overrides a discovered pyproject.toml that disables it."""
+        # arrange
+        source = """
+import pandas as pd
+
+df = pd.read_csv("users.csv", usecols=["user_id"])
+print(df["email"])
+"""
+        with tempfile.TemporaryDirectory() as tmpdir:
+            root = Path(tmpdir)
+            (root / "pyproject.toml").write_text("[tool.typedframes]\nenabled = false\n")
+            config_file = root / "ci.toml"
+            config_file.write_text("[tool.typedframes]\nenabled = true\n")
+            py_file = root / "pipeline.py"
+            py_file.write_text(source)
+
+            # act
+            result = check_file(str(py_file), None, None, str(config_file))
+            errors = json.loads(result)
+
+            # assert
+            self.assertEqual(len(errors), 1)
+            self.assertIn("'email'", errors[0]["message"])
+
+    def test_should_apply_per_call_options_over_project_config(self) -> None:
+        """Test that options_json turns on strict mode and disables a code for that call only."""
+        # arrange
+        source = """
+import pandas as pd
+
+df = pd.read_csv("users.csv", usecols=["user_id"])
+other = load_other()
+merged = df.merge(other, on="user_id")
+print(df["email"])
+"""
+        options = json.dumps({"strict": True, "disable": ["unknown-column"]})
+        with tempfile.TemporaryDirectory() as tmpdir:
+            root = Path(tmpdir)
+            (root / "pyproject.toml").write_text("[tool.typedframes]\nenabled = true\n")
+            py_file = root / "pipeline.py"
+            py_file.write_text(source)
+
+            # act
+            default_codes = [error["code"] for error in json.loads(check_file(str(py_file)))]
+            option_codes = [error["code"] for error in json.loads(check_file(str(py_file), options_json=options))]
+
+            # assert
+            self.assertIn("unknown-column", default_codes)
+            self.assertNotIn("coverage-loss", default_codes)
+            self.assertNotIn("unknown-column", option_codes)
+            self.assertIn("coverage-loss", option_codes)
+
+    def test_should_check_source_text_like_the_file_it_would_be(self) -> None:
+        """Test that check_source returns check_file's output and honours the path's project config."""
+        # arrange
+        source = """
+from typedframes import BaseSchema, Column
+
+class UserSchema(BaseSchema):
+    user_id = Column(type=int)
+
+df: DataFrame[UserSchema] = load()
+print(df["user_idd"])
+"""
+        with tempfile.TemporaryDirectory() as tmpdir:
+            root = Path(tmpdir)
+            py_file = root / "pipeline.py"
+            py_file.write_text(source)
+            disabled = root / "disabled"
+            disabled.mkdir()
+            (disabled / "pyproject.toml").write_text("[tool.typedframes]\nenabled = false\n")
+
+            # act
+            from_file = check_file(str(py_file))
+            from_source = check_source(source, str(py_file))
+            from_disabled = check_source(source, str(disabled / "pipeline.py"))
+
+            # assert
+            self.assertEqual(from_source, from_file)
+            self.assertIn("'user_idd'", from_source)
+            self.assertEqual(from_disabled, "[]")
+
+    def test_should_check_a_directory_by_relative_path(self) -> None:
+        """Test that check_directory lints each file under the root and skips noise directories."""
+        # arrange
+        source = """
+from typedframes import BaseSchema, Column
+
+class UserSchema(BaseSchema):
+    user_id = Column(type=int)
+
+df: DataFrame[UserSchema] = load()
+print(df["user_idd"])
+"""
+        with tempfile.TemporaryDirectory() as tmpdir:
+            root = Path(tmpdir)
+            (root / "pkg").mkdir()
+            (root / "pkg" / "pipeline.py").write_text(source)
+            (root / "clean.py").write_text("x = 1\n")
+            (root / "__pycache__").mkdir()
+            (root / "__pycache__" / "pipeline.py").write_text(source)
+
+            # act
+            result = json.loads(check_directory(str(root)))
+
+            # assert
+            self.assertEqual(sorted(result), ["clean.py", "pkg/pipeline.py"])
+            self.assertEqual(result["clean.py"], [])
+            self.assertIn("'user_idd'", result["pkg/pipeline.py"][0]["message"])
+
+    def test_should_skip_unreadable_files_and_apply_options_in_a_directory(self) -> None:
+        """Test that check_directory leaves out non-UTF-8 files and honours options_json."""
+        # arrange
+        source = """
+from typedframes import BaseSchema, Column
+
+class UserSchema(BaseSchema):
+    user_id = Column(type=int)
+
+df: DataFrame[UserSchema] = load()
+print(df["user_idd"])
+"""
+        with tempfile.TemporaryDirectory() as tmpdir:
+            root = Path(tmpdir)
+            (root / "pipeline.py").write_text(source)
+            (root / "latin1.py").write_bytes(b"name = '\xe9'\n")
+
+            # act
+            default = json.loads(check_directory(str(root)))
+            disabled = json.loads(check_directory(str(root), options_json='{"disable": ["unknown-column"]}'))
+
+            # assert
+            self.assertEqual(sorted(default), ["pipeline.py"])
+            self.assertIn("'user_idd'", default["pipeline.py"][0]["message"])
+            self.assertNotIn("unknown-column", [error["code"] for error in disabled["pipeline.py"]])
+
+    def test_should_reject_unknown_per_call_option(self) -> None:
+        """Test that an unknown options_json key raises ValueError naming the key."""
+        # arrange
+        schemas = json.dumps({"UserSchema": ["user_id"]})
+
+        # act/assert
+        with self.assertRaisesRegex(ValueError, "'strcit'"):
+            lint_snippet("x = 1\n", schemas, '{"strcit": true}')
+
+    def test_should_report_phase_timings_that_grow_with_file_size(self) -> None:
+        """Test that check_file fills the timings dict and a larger file takes longer to parse and lint."""
+        # arrange
+        block = """
+df = pd.read_csv("users.csv", usecols=["user_id", "email"])
+print(df["user_id"], df["email"])
+"""
+        with tempfile.TemporaryDirectory() as tmpdir:
+            small = Path(tmpdir) / "small.py"
+            small.write_text("import pandas as pd\n" + block)
+            large = Path(tmpdir) / "large.py"
+            large.write_text("import pandas as pd\n" + block * 2000)
+            small_timings: dict[str, float] = {}
+            large_timings: dict[str, float] = {}
+
+            # act
+            check_file(str(small), None, None, None, None, small_timings)
+            check_file(str(large), None, None, None, None, large_timings)
+
+            # assert
+            self.assertEqual(set(small_timings), {"parse", "lint", "serialize"})
+            self.assertTrue(all(seconds > 0 for seconds in small_timings.values()))
+            self.assertGreater(large_timings["parse"], small_timings["parse"])
+            self.assertGreater(large_timings["lint"], small_timings["lint"])
+
+    def test_should_not_report_data_position_literals(self) -> None:
+        """Test that map/replace/isin/fillna values on tracked frames are never matched against schemas."""
+        # arrange
+        fixture = str(Path("tests/fixtures/data_position_literals.py").absolute())
+
+        # act
+        result = check_file(fixture)
+
+        # assert
+        self.assertEqual(json.loads(result), [])
+
+    def test_should_lint_snippet_against_seeded_schema(self) -> None:
+        """Test that lint_snippet checks a snippet against a schema it does not define."""
+        # arrange
+        schemas = json.dumps({"UserSchema": ["user_id", "email"]})
+        snippet = 'df: DataFrame[UserSchema] = load()\nprint(df["user_id"])\nprint(df["emial"])\n'
+
+        # act
+        errors = json.loads(lint_snippet(snippet, schemas))
+
+        # assert
+        self.assertEqual([error["line"] for error in errors], [3])
+        self.assertIn("did you mean 'email'?", errors[0]["message"])
+
+    def test_should_reject_malformed_snippet_schemas(self) -> None:
+        """Test that lint_snippet raises ValueError when schemas_json is not a name-to-columns mapping."""
+        # arrange/act/assert
+        with self.assertRaises(ValueError):
+            lint_snippet("x = 1\n", '["UserSchema"]')
+
+    def test_should_lint_many_sources_in_memory(self) -> None:
+        """Test that lint_many_sources resolves schemas across in-memory files keyed by virtual path."""
+        # arrange
+        files = {
+            "virtual/schemas.py": "from typedframes import BaseSchema, Column\n\n"
+            "class UserSchema(BaseSchema):\n    email = Column(type=str)\n",
+            "virtual/users.py": 'df: DataFrame[UserSchema] = load()\nprint(df["emial"])\n',
+            "virtual/report.py": 'df: DataFrame[UserSchema] = load()\nprint(df["email"])\n',
+        }
+
+        # act
+        report = json.loads(lint_many_sources(json.dumps({"files": files})))
+
+        # assert
+        self.assertEqual(report["version"], 1)
+        self.assertEqual(set(report["files"]), set(files))
+        self.assertEqual(report["files"]["virtual/report.py"], [])
+        self.assertEqual([error["line"] for error in report["files"]["virtual/users.py"]], [2])
+        self.assertIn("did you mean 'email'?", report["files"]["virtual/users.py"][0]["message"])
+
+    def test_should_match_analysis_snapshot(self) -> None:
+        """Test that analyze_file returns the documented variable model for a merge, drop and reassignment."""
+        # arrange
+        fixture = str(Path("tests/fixtures/analysis_model.py").absolute())
+        expected = json.loads(Path("tests/fixtures/analysis_model.json").read_text())
+
+        # act
+        analysis = json.loads(analyze_file(fixture))
+
+        # assert
+        self.assertEqual(analysis, expected)
@@ -1,14 +1,28 @@
 """Unit tests for the typedframes CLI."""
 
-import builtins
 import json
+import os
+import sys
 import tempfile
+import types
 import unittest
 from io import StringIO
-from pathlib import Path
+from pathlib import Path, PureWindowsPath
 from unittest.mock import patch
 
-from typedframes.cli import _check_files, _collect_python_files, _format_github, _format_text, main
+from typedframes.cli import (
+    _check_files,
+    _collect_python_files,
+    _display_path,
+    _format_github,
+    _format_text,
+    _manifest_differences,
+    _output_schema,
+    main,
+)
+
+# What a stand-in Rust module's `resolve_settings` reports: strict mode off by default.
+_DEFAULT_SETTINGS = json.dumps({"settings": {"strict": {"value": False, "source": "default"}}})
 
 
 class TestCli(unittest.TestCase):
@@ -46,13 +60,6 @@ class TestCli(unittest.TestCase):
     def test_should_exit_1_when_checker_not_installed(self) -> None:
         """Test that a helpful error is shown when typedframes-checker is missing."""
         # arrange
-        original_import = builtins.__import__
-
-        def mock_import(name: str, *args: object, **kwargs: object) -> object:
-            if name == "typedframes._rust_checker":
-                raise ImportError(name)
-            return original_import(name, *args, **kwargs)
-
         with tempfile.TemporaryDirectory() as tmpdir:
             py_file = Path(tmpdir) / "test.py"
             py_file.write_text("x = 1\n")
@@ -61,7 +68,7 @@ class TestCli(unittest.TestCase):
 
             # act / assert
             with (
-                patch("builtins.__import__", side_effect=mock_import),
+                patch.dict(sys.modules, {"typedframes._rust_checker": None}),
                 patch("sys.stderr", captured),
                 self.assertRaises(SystemExit) as ctx,
             ):
@@ -120,6 +127,17 @@ class TestCli(unittest.TestCase):
             self.assertIn("a.py", names)
             self.assertIn("c.py", names)
 
+    def test_should_display_windows_paths_with_forward_slashes(self) -> None:
+        """Test that output paths use forward slashes whatever the platform's separator."""
+        # arrange
+        path = PureWindowsPath("src\\pkg\\pipeline.py")
+
+        # act
+        result = _display_path(path)
+
+        # assert
+        self.assertEqual("src/pkg/pipeline.py", result)
+
     def test_should_format_text_errors(self) -> None:
         """Test text error formatting uses ty-style file:line:col: severity[code] message."""
         # arrange
@@ -228,6 +246,85 @@ class TestCli(unittest.TestCase):
         self.assertIn("::error file=src/foo.py,line=42,col=8,title=unknown-column::Column 'x' not in Schema", result)
         self.assertIn("::warning file=src/bar.py,line=10,col=1,title=untracked-dataframe::columns unknown", result)
 
+    def test_should_format_info_as_github_notice(self) -> None:
+        """Test that info diagnostics map to the GitHub Actions notice command."""
+        # arrange
+        errors = [
+            {
+                "file": "src/foo.py",
+                "line": 2,
+                "col": 1,
+                "code": "star-import",
+                "message": "star import not resolved",
+                "severity": "info",
+            },
+        ]
+
+        # act
+        result = _format_github(errors)
+
+        # assert
+        self.assertEqual("::notice file=src/foo.py,line=2,col=1,title=star-import::star import not resolved", result)
+
+    def test_should_keep_quoted_column_names_intact_in_text_output(self) -> None:
+        """Test that quotes, backslashes and non-ASCII names pass through and line breaks are escaped."""
+        # arrange
+        errors = [
+            {
+                "file": "src/foo.py",
+                "line": 3,
+                "col": 7,
+                "code": "unknown-column",
+                "message": "Column 'O\\'Brien\\'s \"score\"' does not exist in S (defined at line 2)",
+                "severity": "error",
+            },
+            {
+                "file": "src/foo.py",
+                "line": 4,
+                "col": 7,
+                "code": "unknown-column",
+                "message": "Column 'a\\\\b' does not exist in {größe, x\ny}",
+                "severity": "error",
+            },
+        ]
+
+        # act
+        result = _format_text(errors)
+
+        # assert
+        self.assertEqual(
+            result.splitlines(),
+            [
+                "src/foo.py:3:7: error[unknown-column] Column 'O\\'Brien\\'s \"score\"' does not exist in S "
+                "(defined at line 2)",
+                "src/foo.py:4:7: error[unknown-column] Column 'a\\\\b' does not exist in {größe, x\\ny}",
+            ],
+        )
+
+    def test_should_escape_github_workflow_command_special_characters(self) -> None:
+        """Test that %, line breaks, and property separators are escaped in GitHub annotations."""
+        # arrange
+        errors = [
+            {
+                "file": "src/a,b:c.py",
+                "line": 5,
+                "col": 2,
+                "code": "unknown-column",
+                "message": "Column 'größe 100%' does not exist in {x\ny}",
+                "severity": "error",
+            },
+        ]
+
+        # act
+        result = _format_github(errors)
+
+        # assert
+        self.assertEqual(
+            "::error file=src/a%2Cb%3Ac.py,line=5,col=2,title=unknown-column::"
+            "Column 'größe 100%25' does not exist in {x%0Ay}",
+            result,
+        )
+
     def test_should_output_json_when_flag_set(self) -> None:
         """Test JSON output mode via --json flag."""
         # arrange
@@ -576,13 +673,6 @@ class TestCli(unittest.TestCase):
     def test_should_not_crash_when_checker_not_installed_on_directory(self) -> None:
         """Test that a missing Rust extension when checking a directory exits with code 1."""
         # arrange
-        original_import = builtins.__import__
-
-        def mock_import(name: str, *args: object, **kwargs: object) -> object:
-            if name == "typedframes._rust_checker":
-                raise ImportError(name)
-            return original_import(name, *args, **kwargs)
-
         with tempfile.TemporaryDirectory() as tmpdir:
             py_file = Path(tmpdir) / "test.py"
             py_file.write_text("x = 1\n")
@@ -591,7 +681,7 @@ class TestCli(unittest.TestCase):
 
             # act / assert
             with (
-                patch("builtins.__import__", side_effect=mock_import),
+                patch.dict(sys.modules, {"typedframes._rust_checker": None}),
                 patch("sys.stderr", captured),
                 self.assertRaises(SystemExit) as ctx,
             ):
@@ -599,3 +689,599 @@ class TestCli(unittest.TestCase):
 
             self.assertEqual(ctx.exception.code, 1)
             self.assertIn("Rust checker extension was not found", captured.getvalue())
+
+    def test_should_print_explanation_trail_with_explain_findings_flag(self) -> None:
+        """Test that --explain-findings is passed to the checker and the trail is printed."""
+        # arrange
+        error = {
+            "file": "f.py",
+            "line": 9,
+            "col": 5,
+            "code": "unknown-column",
+            "message": "Column 'email' does not exist",
+            "severity": "error",
+            "explanation": {
+                "variable": "slim",
+                "schema": "__inferred_slim_at_7",
+                "columns": ["name", "user_id"],
+                "total_columns": 2,
+                "bound_via": "drop",
+                "bound_at": 7,
+                "transforms": [{"kind": "drop", "line": 7, "detail": "email"}],
+            },
+        }
+        with tempfile.TemporaryDirectory() as tmpdir:
+            py_file = Path(tmpdir) / "f.py"
+            py_file.write_text("x = 1\n")
+            captured = StringIO()
+
+            # act
+            with (
+                patch("typedframes.cli._check_files", return_value=[error]) as check_files,
+                patch("sys.stdout", captured),
+            ):
+                main(["check", str(py_file), "--explain-findings"])
+
+            # assert
+            self.assertTrue(check_files.call_args.kwargs["explain_findings"])
+            output = captured.getvalue()
+            self.assertIn("  = note: 'slim' bound via drop at line 7", output)
+            self.assertIn("  = note: known columns: {name, user_id}", output)
+            self.assertIn("  = note: line 7: drop email", output)
+
+    def test_should_pass_config_path_to_checker(self) -> None:
+        """Test that --config is resolved and passed through to the checker."""
+        # arrange
+        with tempfile.TemporaryDirectory() as tmpdir:
+            py_file = Path(tmpdir) / "f.py"
+            py_file.write_text("x = 1\n")
+            config_file = Path(tmpdir) / "ci.toml"
+            config_file.write_text("[tool.typedframes]\nenabled = false\n")
+            captured = StringIO()
+
+            # act
+            with (
+                patch("typedframes.cli._check_files", return_value=[]) as check_files,
+                patch("sys.stdout", captured),
+            ):
+                main(["check", str(py_file), "--config", str(config_file)])
+
+            # assert
+            self.assertEqual(check_files.call_args.kwargs["config_path"], config_file.resolve())
+
+    def test_should_exit_2_for_nonexistent_config(self) -> None:
+        """Test that --config pointing at a missing file exits with code 2."""
+        # arrange
+        with tempfile.TemporaryDirectory() as tmpdir:
+            py_file = Path(tmpdir) / "f.py"
+            py_file.write_text("x = 1\n")
+            captured = StringIO()
+
+            # act / assert
+            with (
+                patch("sys.stderr", captured),
+                self.assertRaises(SystemExit) as ctx,
+            ):
+                main(["check", str(py_file), "--config", str(Path(tmpdir) / "missing.toml")])
+            self.assertEqual(ctx.exception.code, 2)
+            self.assertIn("config file does not exist", captured.getvalue())
+
+    def test_should_show_settings_with_their_sources(self) -> None:
+        """Test that --show-settings prints each effective value with the layer that set it."""
+        # arrange
+        with tempfile.TemporaryDirectory() as tmpdir:
+            py_file = Path(tmpdir) / "f.py"
+            py_file.write_text("x = 1\n")
+            report = {
+                "file": str(py_file),
+                "config_file": str(Path(tmpdir) / "pyproject.toml"),
+                "settings": {
+                    "enabled": {"value": False, "source": "config-file"},
+                    "strict": {"value": True, "source": "argument"},
+                },
+            }
+            captured = StringIO()
+
+            # act
+            with (
+                patch("typedframes.cli._resolve_settings", return_value=report) as resolve,
+                patch("typedframes.cli._check_files") as check_files,
+                patch("sys.stdout", captured),
+            ):
+                main(["check", str(py_file), "--show-settings", "--strict"])
+
+            # assert
+            check_files.assert_not_called()
+            self.assertIs(resolve.call_args.kwargs["strict"], True)
+            self.assertIsNone(resolve.call_args.kwargs["explain_findings"])
+            output = captured.getvalue()
+            self.assertIn("  enabled = false  (config-file)", output)
+            self.assertIn("  strict  = true  (argument)", output)
+
+    def test_should_exit_1_when_strict_env_var_set_and_errors(self) -> None:
+        """Test that TYPEDFRAMES_STRICT=1 behaves like --strict."""
+        # arrange
+        error = {"file": "f.py", "line": 1, "col": 0, "severity": "error", "code": "unknown-column", "message": "bad"}
+        with tempfile.TemporaryDirectory() as tmpdir:
+            py_file = Path(tmpdir) / "f.py"
+            py_file.write_text("x = 1\n")
+
+            # act / assert
+            with (
+                patch.dict(os.environ, {"TYPEDFRAMES_STRICT": "1"}),
+                patch("typedframes.cli._check_files", return_value=[error]),
+                patch("sys.stdout", StringIO()),
+                self.assertRaises(SystemExit) as ctx,
+            ):
+                main(["check", str(py_file)])
+            self.assertEqual(ctx.exception.code, 1)
+
+    def test_should_exit_2_for_malformed_strict_env_var(self) -> None:
+        """Test that a malformed TYPEDFRAMES_STRICT value is reported instead of ignored."""
+        # arrange
+        with tempfile.TemporaryDirectory() as tmpdir:
+            py_file = Path(tmpdir) / "f.py"
+            py_file.write_text("x = 1\n")
+            captured = StringIO()
+
+            # act / assert
+            with (
+                patch.dict(os.environ, {"TYPEDFRAMES_STRICT": "maybe"}),
+                patch("sys.stderr", captured),
+                self.assertRaises(SystemExit) as ctx,
+            ):
+                main(["check", str(py_file)])
+            self.assertEqual(ctx.exception.code, 2)
+            self.assertIn("TYPEDFRAMES_STRICT", captured.getvalue())
+
+    def test_should_request_coverage_loss_notes_only_in_strict_mode(self) -> None:
+        """Test that strict mode is passed through to the checker and is off by default."""
+        # arrange
+        with tempfile.TemporaryDirectory() as tmpdir:
+            py_file = Path(tmpdir) / "f.py"
+            py_file.write_text("x = 1\n")
+
+            # act
+            with (
+                patch("typedframes.cli._check_files", return_value=[]) as check_files,
+                patch("sys.stdout", StringIO()),
+            ):
+                main(["check", str(py_file)])
+                main(["check", str(py_file), "--strict"])
+
+            # assert
+            self.assertFalse(check_files.call_args_list[0].kwargs["strict"])
+            self.assertTrue(check_files.call_args_list[1].kwargs["strict"])
+
+    def test_should_print_slowest_files_with_stats_flag(self) -> None:
+        """Test that --stats appends total time and the slowest files, slowest first."""
+        # arrange
+        def fake_check_files(files: list[Path], **kwargs: object) -> list[dict]:
+            timings = kwargs["timings"]
+            assert isinstance(timings, list)
+            for i, file_path in enumerate(files):
+                total = 0.5 if file_path.name == "big.py" else 0.01 * (i + 1)
+                timings.append(
+                    {"file": str(file_path), "bytes": 10, "total": total, "parse": 0.0, "lint": total, "serialize": 0.0}
+                )
+            return []
+
+        with tempfile.TemporaryDirectory() as tmpdir:
+            for name in ("a.py", "big.py", "c.py"):
+                (Path(tmpdir) / name).write_text("x = 1\n")
+            captured = StringIO()
+
+            # act
+            with (
+                patch("typedframes.cli._check_files", side_effect=fake_check_files),
+                patch("sys.stdout", captured),
+            ):
+                main(["check", tmpdir, "--no-index", "--stats"])
+
+            # assert
+            lines = captured.getvalue().splitlines()
+            stats_at = next(i for i, line in enumerate(lines) if line.startswith("Stats: checked 3 files in "))
+            self.assertEqual(lines[stats_at + 1], "Slowest 3:")
+            self.assertIn("big.py (10 bytes; parse 0.000s, lint 0.500s, serialize 0.000s)", lines[stats_at + 2])
+            self.assertTrue(lines[stats_at + 2].lstrip().startswith("0.500s"))
+
+    def test_should_write_per_file_profile_json(self) -> None:
+        """Test that --profile writes the per-file phase breakdown without printing stats."""
+        # arrange
+        def fake_check_file(*args: object) -> str:
+            phases = args[5]
+            assert isinstance(phases, dict)
+            phases.update(parse=0.001, lint=0.002, serialize=0.0005)
+            return "[]"
+
+        with tempfile.TemporaryDirectory() as tmpdir:
+            py_file = Path(tmpdir) / "f.py"
+            py_file.write_text("x = 1\n")
+            profile = Path(tmpdir) / "profile.json"
+            checker = types.SimpleNamespace(
+                check_file=fake_check_file, resolve_settings=lambda *args: _DEFAULT_SETTINGS
+            )
+            captured = StringIO()
+
+            # act
+            with (
+                patch.dict(sys.modules, {"typedframes._rust_checker": checker}),
+                patch("sys.stdout", captured),
+            ):
+                main(["check", str(py_file), "--profile", str(profile)])
+
+            # assert
+            report = json.loads(profile.read_text())
+            self.assertGreater(report["total"], 0)
+            [entry] = report["files"]
+            self.assertEqual(entry["file"], str(py_file.resolve()))
+            self.assertEqual(entry["bytes"], 6)
+            self.assertEqual((entry["parse"], entry["lint"], entry["serialize"]), (0.001, 0.002, 0.0005))
+            self.assertGreater(entry["total"], 0)
+            self.assertNotIn("Stats:", captured.getvalue())
+
+    def test_should_drop_diagnostics_below_min_severity_in_json_output(self) -> None:
+        """Test that --min-severity error removes warnings from JSON output."""
+        # arrange
+        warning = {"line": 1, "col": 0, "severity": "warning", "code": "coverage-loss", "message": "w"}
+        error = {"line": 2, "col": 0, "severity": "error", "code": "unknown-column", "message": "e"}
+        with tempfile.TemporaryDirectory() as tmpdir:
+            py_file = Path(tmpdir) / "f.py"
+            py_file.write_text("x = 1\n")
+            captured = StringIO()
+
+            # act
+            with (
+                patch("typedframes.cli._check_files", return_value=[warning, error]),
+                patch("sys.stdout", captured),
+            ):
+                main(["check", str(py_file), "--output-format", "json", "--min-severity", "error"])
+
+            # assert
+            self.assertEqual(json.loads(captured.getvalue()), [error])
+
+    def test_should_print_only_summary_table_with_summary_only(self) -> None:
+        """Test that --summary-only prints per-severity and per-code counts and the exit code, not diagnostics."""
+        # arrange
+        diagnostics = [
+            {"line": 1, "col": 0, "severity": "error", "code": "unknown-column", "message": "first"},
+            {"line": 2, "col": 0, "severity": "error", "code": "unknown-column", "message": "second"},
+            {"line": 3, "col": 0, "severity": "warning", "code": "coverage-loss", "message": "third"},
+        ]
+        with tempfile.TemporaryDirectory() as tmpdir:
+            py_file = Path(tmpdir) / "f.py"
+            py_file.write_text("x = 1\n")
+            captured = StringIO()
+
+            # act
+            with (
+                patch("typedframes.cli._check_files", return_value=diagnostics),
+                patch("sys.stdout", captured),
+                self.assertRaises(SystemExit) as ctx,
+            ):
+                main(["check", str(py_file), "--summary-only", "--strict"])
+
+            # assert
+            self.assertEqual(ctx.exception.code, 1)
+            self.assertEqual(
+                captured.getvalue().splitlines(),
+                [
+                    "Checked 1 file",
+                    "  error    2",
+                    "  warning  1",
+                    "By code:",
+                    "  coverage-loss  1",
+                    "  unknown-column 2",
+                    "Exit code: 1",
+                ],
+            )
+
+    def test_should_print_summary_object_with_summary_only_json(self) -> None:
+        """Test that --summary-only with JSON output prints the summary object after severity filtering."""
+        # arrange
+        diagnostics = [
+            {"line": 1, "col": 0, "severity": "error", "code": "unknown-column", "message": "first"},
+            {"line": 3, "col": 0, "severity": "warning", "code": "coverage-loss", "message": "third"},
+        ]
+        with tempfile.TemporaryDirectory() as tmpdir:
+            py_file = Path(tmpdir) / "f.py"
+            py_file.write_text("x = 1\n")
+            captured = StringIO()
+
+            # act
+            with (
+                patch("typedframes.cli._check_files", return_value=diagnostics),
+                patch("sys.stdout", captured),
+            ):
+                main(["check", str(py_file), "--summary-only", "--output-format", "json", "--min-severity", "error"])
+
+            # assert
+            self.assertEqual(
+                json.loads(captured.getvalue()),
+                {"files": 1, "by_severity": {"error": 1}, "by_code": {"unknown-column": 1}, "exit_code": 0},
+            )
+
+    def test_should_print_config_findings_and_exit_1(self) -> None:
+        """Test that config-check prints findings at the config file and exits 1 on errors."""
+        # arrange
+        finding = {
+            "line": 3,
+            "col": 1,
+            "severity": "error",
+            "code": "unknown-config-key",
+            "message": "unknown key 'mutaton-mode' in [tool.typedframes] (did you mean 'mutation-mode'?)",
+        }
+        with tempfile.TemporaryDirectory() as tmpdir:
+            config_file = Path(tmpdir) / "pyproject.toml"
+            config_file.write_text("[tool.typedframes]\n")
+            captured = StringIO()
+
+            # act
+            with (
+                patch("typedframes.cli._validate_config", return_value=[finding]),
+                patch("sys.stdout", captured),
+                self.assertRaises(SystemExit) as ctx,
+            ):
+                main(["config-check", tmpdir])
+
+            # assert
+            self.assertEqual(ctx.exception.code, 1)
+            output = captured.getvalue()
+            self.assertIn(f"{config_file.resolve()}:3:1: error[unknown-config-key]", output)
+            self.assertIn("Found 1 configuration problem", output)
+
+    def test_should_report_valid_config(self) -> None:
+        """Test that config-check exits 0 when the validator finds nothing."""
+        # arrange
+        with tempfile.TemporaryDirectory() as tmpdir:
+            config_file = Path(tmpdir) / "ci.toml"
+            config_file.write_text("[tool.typedframes]\nwarnings = false\n")
+            captured = StringIO()
+
+            # act
+            with (
+                patch("typedframes.cli._validate_config", return_value=[]) as validate,
+                patch("sys.stdout", captured),
+            ):
+                main(["config-check", "--config", str(config_file)])
+
+            # assert
+            validate.assert_called_once_with(config_file.resolve())
+            self.assertIn("is valid", captured.getvalue())
+
+    def test_should_exit_2_when_config_check_finds_no_pyproject(self) -> None:
+        """Test that config-check exits 2 when the project has no pyproject.toml."""
+        # arrange
+        with tempfile.TemporaryDirectory() as tmpdir:
+            captured = StringIO()
+
+            # act / assert
+            with (
+                patch("sys.stderr", captured),
+                self.assertRaises(SystemExit) as ctx,
+            ):
+                main(["config-check", tmpdir])
+            self.assertEqual(ctx.exception.code, 2)
+            self.assertIn("config file does not exist", captured.getvalue())
+
+    def test_should_print_output_schema(self) -> None:
+        """Test that --output-schema prints the versioned JSON Schema of the json output."""
+        # arrange
+        captured = StringIO()
+
+        # act
+        with patch("sys.stdout", captured):
+            main(["--output-schema"])
+
+        # assert
+        schema = json.loads(captured.getvalue())
+        self.assertEqual(schema["version"], 1)
+        self.assertEqual(schema["$defs"]["diagnostic"]["required"], ["line", "col", "code", "message", "severity"])
+
+    def test_should_describe_golden_output_with_output_schema(self) -> None:
+        """Test that every key of the previous release's output is declared, in order, by the schema."""
+        # arrange
+        schema = json.loads(_output_schema())
+        golden = json.loads(Path("tests/fixtures/lint_output_v1.json").read_text())
+        defs = schema["$defs"]
+
+        # act
+        diagnostic_order = list(defs["diagnostic"]["properties"])
+        explanation_order = list(defs["explanation"]["properties"])
+
+        # assert
+        for error in golden:
+            self.assertEqual(list(error), [key for key in diagnostic_order if key in error])
+            self.assertTrue(set(defs["diagnostic"]["required"]) <= set(error))
+            explanation = error.get("explanation")
+            if explanation:
+                self.assertEqual(list(explanation), explanation_order)
+
+    def _fake_manifest_checker(self) -> types.SimpleNamespace:
+        """Return a stand-in Rust module whose run_manifest fingerprints files by their contents."""
+
+        def fake_run_manifest(path: str, counts_json: str, *args: object) -> str:
+            root = Path(path)
+            files = {p.name: f"len:{len(p.read_text())}" for p in sorted(root.glob("*.py"))}
+            manifest = {
+                "version": 1,
+                "tool_version": "0.3.1",
+                "parser": "ruff_python_parser 0.15.0",
+                "settings": {"enabled": True},
+                "files": files,
+                "schema_fingerprint": "fnv1a64:0",
+                "counts": json.loads(counts_json),
+            }
+            return json.dumps(manifest)
+
+        return types.SimpleNamespace(
+            check_file=lambda *args: "[]",
+            resolve_settings=lambda *args: _DEFAULT_SETTINGS,
+            run_manifest=fake_run_manifest,
+        )
+
+    def test_should_verify_an_unchanged_run_against_its_manifest(self) -> None:
+        """Test that --verify-manifest passes for a run identical to the one --write-manifest recorded."""
+        # arrange
+        with tempfile.TemporaryDirectory() as tmpdir:
+            root = Path(tmpdir) / "src"
+            root.mkdir()
+            (root / "a.py").write_text("x = 1\n")
+            manifest = Path(tmpdir) / "manifest.json"
+            checker = self._fake_manifest_checker()
+            captured = StringIO()
+
+            # act
+            with (
+                patch.dict(sys.modules, {"typedframes._rust_checker": checker}),
+                patch("sys.stdout", captured),
+            ):
+                main(["check", str(root), "--write-manifest", str(manifest)])
+                main(["check", str(root), "--verify-manifest", str(manifest)])
+
+            # assert
+            self.assertEqual(json.loads(manifest.read_text())["files"], {"a.py": "len:6"})
+            self.assertIn("Run matches manifest", captured.getvalue())
+
+    def test_should_fail_verification_naming_the_edited_file(self) -> None:
+        """Test that --verify-manifest exits 1 and names the file edited since the manifest was written."""
+        # arrange
+        with tempfile.TemporaryDirectory() as tmpdir:
+            root = Path(tmpdir) / "src"
+            root.mkdir()
+            (root / "a.py").write_text("x = 1\n")
+            (root / "b.py").write_text("y = 2\n")
+            manifest = Path(tmpdir) / "manifest.json"
+            checker = self._fake_manifest_checker()
+            captured = StringIO()
+            with (
+                patch.dict(sys.modules, {"typedframes._rust_checker": checker}),
+                patch("sys.stdout", StringIO()),
+            ):
+                main(["check", str(root), "--write-manifest", str(manifest)])
+            (root / "b.py").write_text("y = 22\n")
+
+            # act
+            with (
+                patch.dict(sys.modules, {"typedframes._rust_checker": checker}),
+                patch("sys.stdout", captured),
+                self.assertRaises(SystemExit) as ctx,
+            ):
+                main(["check", str(root), "--verify-manifest", str(manifest)])
+
+            # assert
+            self.assertEqual(ctx.exception.code, 1)
+            self.assertIn("file changed: b.py", captured.getvalue())
+            self.assertNotIn("a.py", captured.getvalue())
+
+    def test_should_describe_config_drift_and_count_changes_between_manifests(self) -> None:
+        """Test that _manifest_differences reports setting, registry and per-code count changes."""
+        # arrange
+        expected = {
+            "tool_version": "0.3.1",
+            "parser": "p",
+            "settings": {"strict": False},
+            "files": {"a.py": "h1"},
+            "schema_fingerprint": "s1",
+            "counts": {"unknown-column": 2},
+        }
+        actual = {
+            "tool_version": "0.3.1",
+            "parser": "p",
+            "settings": {"strict": True},
+            "files": {"a.py": "h1", "c.py": "h3"},
+            "schema_fingerprint": "s2",
+            "counts": {"unknown-column": 1, "dropped-column": 1},
+        }
+
+        # act
+        differences = _manifest_differences(expected, actual)
+
+        # assert
+        self.assertEqual(
+            differences,
+            [
+                "setting changed: strict: false -> true",
+                "file added: c.py",
+                "schema registry changed",
+                "count changed: dropped-column 0 -> 1",
+                "count changed: unknown-column 2 -> 1",
+            ],
+        )
+
+    def _fake_annotate_checker(self) -> types.SimpleNamespace:
+        """Return a stand-in Rust module proposing one unique and one ambiguous annotation."""
+        suggestions = [
+            {
+                "variable": "users",
+                "scope": "<module>",
+                "line": 2,
+                "col": 6,
+                "columns": ["email"],
+                "schemas": ["UserSchema"],
+                "annotation": ": PandasFrame[UserSchema]",
+            },
+            {
+                "variable": "ids",
+                "scope": "<module>",
+                "line": 4,
+                "col": 4,
+                "columns": ["user_id"],
+                "schemas": ["OrderSchema", "UserSchema"],
+                "annotation": None,
+            },
+        ]
+        return types.SimpleNamespace(
+            build_project_index=lambda *args: None,
+            suggest_annotations=lambda *args: json.dumps(suggestions),
+        )
+
+    def test_should_apply_only_uniquely_matched_annotations(self) -> None:
+        """Test that annotate --schema-match --apply edits the unique match and reports the ambiguous one."""
+        # arrange
+        source = (
+            'import pandas as pd\nusers = pd.read_csv("u.csv")\nprint(users["email"])\nids = pd.read_csv("i.csv")\n'
+        )
+        with tempfile.TemporaryDirectory() as tmpdir:
+            py_file = Path(tmpdir) / "load.py"
+            py_file.write_text(source)
+            captured = StringIO()
+
+            # act
+            with (
+                patch.dict(sys.modules, {"typedframes._rust_checker": self._fake_annotate_checker()}),
+                patch("sys.stdout", captured),
+            ):
+                main(["annotate", str(py_file), "--schema-match", "--apply"])
+
+            # assert
+            lines = py_file.read_text().splitlines()
+            self.assertEqual(lines[1], 'users: PandasFrame[UserSchema] = pd.read_csv("u.csv")')
+            self.assertEqual(lines[3], 'ids = pd.read_csv("i.csv")')
+            output = captured.getvalue()
+            ambiguous = "'ids' not annotated: columns {user_id} fit several schemas (OrderSchema, UserSchema)"
+            self.assertIn(ambiguous, output)
+            self.assertIn("1 annotation(s) applied, 1 ambiguous, 0 unmatched", output)
+
+    def test_should_only_list_annotations_without_apply(self) -> None:
+        """Test that annotate --schema-match leaves files untouched unless --apply is given."""
+        # arrange
+        source = (
+            'import pandas as pd\nusers = pd.read_csv("u.csv")\nprint(users["email"])\nids = pd.read_csv("i.csv")\n'
+        )
+        with tempfile.TemporaryDirectory() as tmpdir:
+            py_file = Path(tmpdir) / "load.py"
+            py_file.write_text(source)
+            captured = StringIO()
+
+            # act
+            with (
+                patch.dict(sys.modules, {"typedframes._rust_checker": self._fake_annotate_checker()}),
+                patch("sys.stdout", captured),
+            ):
+                main(["annotate", str(py_file), "--schema-match"])
+
+            # assert
+            self.assertEqual(py_file.read_text(), source)
+            self.assertIn("load.py:2:6: users: PandasFrame[UserSchema]", captured.getvalue())
+            self.assertIn("1 annotation(s) proposed", captured.getvalue())
@@ -2,13 +2,24 @@
 
 import json
 import sys
+import tempfile
 import unittest
 from pathlib import Path
 from unittest.mock import MagicMock, patch
 
 from mypy.options import Options
 
-from typedframes.mypy import CheckerNotFoundError, TypedFramesPlugin, plugin
+from typedframes.mypy import CachedResult, CacheStats, CheckerNotFoundError, TypedFramesPlugin, plugin
+
+
+def _checker_output(errors: list[dict], dependencies: tuple[tuple[str, str, str], ...] = ()) -> str:
+    """Return checker output in the shape ``check_file_with_dependencies`` produces."""
+    return json.dumps(
+        {
+            "diagnostics": errors,
+            "dependencies": [{"schema": schema, "file": file, "hash": hash_} for schema, file, hash_ in dependencies],
+        }
+    )
 
 
 class TestTypedFramesPluginUnit(unittest.TestCase):
@@ -24,12 +35,14 @@ class TestTypedFramesPluginUnit(unittest.TestCase):
     def test_should_report_error_on_exact_line_match(self) -> None:
         """Test that errors are reported on exact line matches."""
         # arrange - mock the rust extension
-        mock_check_file = MagicMock(return_value=json.dumps(self.error_data))
+        mock_check_file = MagicMock(return_value=_checker_output(self.error_data))
 
         with (
             patch("typedframes.mypy.get_project_root") as mock_root,
             patch("typedframes.mypy.is_enabled") as mock_enabled,
-            patch.dict(sys.modules, {"typedframes._rust_checker": MagicMock(check_file=mock_check_file)}),
+            patch.dict(
+                sys.modules, {"typedframes._rust_checker": MagicMock(check_file_with_dependencies=mock_check_file)}
+            ),
         ):
             mock_enabled.return_value = True
             mock_root.return_value = Path()
@@ -49,12 +62,15 @@ class TestTypedFramesPluginUnit(unittest.TestCase):
 
         # Test non-match branch
         new_plugin = TypedFramesPlugin(Options())  # New plugin to avoid cache
-        mock_check_file_empty = MagicMock(return_value=json.dumps([]))
+        mock_check_file_empty = MagicMock(return_value=_checker_output([]))
 
         with (
             patch("typedframes.mypy.get_project_root") as mock_root,
             patch("typedframes.mypy.is_enabled") as mock_enabled,
-            patch.dict(sys.modules, {"typedframes._rust_checker": MagicMock(check_file=mock_check_file_empty)}),
+            patch.dict(
+                sys.modules,
+                {"typedframes._rust_checker": MagicMock(check_file_with_dependencies=mock_check_file_empty)},
+            ),
         ):
             mock_enabled.return_value = True
             mock_root.return_value = Path()
@@ -73,12 +89,14 @@ class TestTypedFramesPluginUnit(unittest.TestCase):
     def test_should_report_error_on_fuzzy_line_match(self) -> None:
         """Test that errors are reported on fuzzy line matches within tolerance."""
         # arrange - mock the rust extension
-        mock_check_file = MagicMock(return_value=json.dumps(self.error_data))
+        mock_check_file = MagicMock(return_value=_checker_output(self.error_data))
 
         with (
             patch("typedframes.mypy.get_project_root") as mock_root,
             patch("typedframes.mypy.is_enabled") as mock_enabled,
-            patch.dict(sys.modules, {"typedframes._rust_checker": MagicMock(check_file=mock_check_file)}),
+            patch.dict(
+                sys.modules, {"typedframes._rust_checker": MagicMock(check_file_with_dependencies=mock_check_file)}
+            ),
         ):
             mock_enabled.return_value = True
             mock_root.return_value = Path()
@@ -124,7 +142,7 @@ class TestTypedFramesPluginUnit(unittest.TestCase):
     def test_should_return_cached_results(self) -> None:
         """Test that cached results are returned for previously checked files."""
         # arrange
-        self.plugin._checker_results[self.test_file] = self.error_data
+        self.plugin._checker_results[self.test_file] = CachedResult(self.error_data, frozenset())
 
         # act
         errors = self.plugin._run_checker(self.test_file)
@@ -163,13 +181,15 @@ class TestTypedFramesPluginUnit(unittest.TestCase):
         """Test that no error is reported when access line is far from all error lines."""
         # arrange
         far_error_data = [{"line": 100, "message": "Column 'bar' does not exist"}]
-        mock_check_file = MagicMock(return_value=json.dumps(far_error_data))
+        mock_check_file = MagicMock(return_value=_checker_output(far_error_data))
         new_plugin = TypedFramesPlugin(Options())
 
         with (
             patch("typedframes.mypy.get_project_root") as mock_root,
             patch("typedframes.mypy.is_enabled") as mock_enabled,
-            patch.dict(sys.modules, {"typedframes._rust_checker": MagicMock(check_file=mock_check_file)}),
+            patch.dict(
+                sys.modules, {"typedframes._rust_checker": MagicMock(check_file_with_dependencies=mock_check_file)}
+            ),
         ):
             mock_enabled.return_value = True
             mock_root.return_value = Path()
@@ -222,3 +242,128 @@ class TestTypedFramesPluginUnit(unittest.TestCase):
         mock_build.assert_called_once()
         self.assertEqual(first, b"fake-msgpack")
         self.assertEqual(second, b"fake-msgpack")
+
+    def test_should_recheck_project_files_when_a_checked_file_changes(self) -> None:
+        """Test that editing a checked file refreshes the index and re-checks the cached files that read its schemas."""
+        # arrange
+        with tempfile.TemporaryDirectory() as tmpdir:
+            root = Path(tmpdir).resolve()
+            (root / "pyproject.toml").write_text("[tool.typedframes]\n")
+            schemas = root / "schemas.py"
+            schemas.write_text("class S(BaseSchema):\n    a = Column(type=int)\n")
+            pipeline = root / "pipeline.py"
+            pipeline.write_text("df['a']\n")
+            mock_check_file = MagicMock(
+                side_effect=[
+                    _checker_output([]),
+                    _checker_output([], (("S", str(schemas), "fnv1a64:1"),)),
+                    _checker_output([]),
+                    _checker_output(self.error_data, (("S", str(schemas), "fnv1a64:2"),)),
+                ]
+            )
+            mock_update = MagicMock(return_value=b"updated-index")
+            checker = MagicMock(
+                check_file_with_dependencies=mock_check_file,
+                build_project_index=MagicMock(return_value=b"index"),
+                update_project_index=mock_update,
+                schema_hashes=MagicMock(
+                    return_value=json.dumps([{"schema": "S", "file": str(schemas), "hash": "fnv1a64:2"}])
+                ),
+            )
+            new_plugin = TypedFramesPlugin(Options())
+
+            with patch.dict(sys.modules, {"typedframes._rust_checker": checker}):
+                new_plugin._run_checker(str(schemas))
+                new_plugin._run_checker(str(pipeline))
+
+                # act
+                schemas.write_text("class S(BaseSchema):\n    amount = Column(type=int)\n")
+                new_plugin._run_checker(str(schemas))
+                errors = new_plugin._run_checker(str(pipeline))
+
+        # assert
+        mock_update.assert_called_once_with(b"index", str(root), str(schemas))
+        self.assertEqual(new_plugin._index_bytes_by_root[str(root)], b"updated-index")
+        self.assertEqual(mock_check_file.call_count, 4)
+        self.assertEqual(mock_check_file.call_args.args, (str(pipeline), b"updated-index"))
+        self.assertEqual(errors, self.error_data)
+
+    def test_should_invalidate_only_the_results_that_read_an_edited_schema(self) -> None:
+        """Test that a second run is served from the cache and a schema edit re-checks exactly its consumer."""
+        # arrange
+        with tempfile.TemporaryDirectory() as tmpdir:
+            root = Path(tmpdir).resolve()
+            (root / "pyproject.toml").write_text("[tool.typedframes]\n")
+            schemas = root / "schemas.py"
+            schemas.write_text("class UserSchema(BaseSchema):\n    email = Column(type=str)\n")
+            consumer = root / "consumer.py"
+            consumer.write_text("users['email']\n")
+            report = root / "report.py"
+            report.write_text("orders['total']\n")
+            user_v1 = ("UserSchema", str(schemas), "fnv1a64:1")
+            user_v2 = ("UserSchema", str(schemas), "fnv1a64:2")
+            orders = ("OrderSchema", str(schemas), "fnv1a64:3")
+            outputs = {
+                str(schemas): [_checker_output([]), _checker_output([])],
+                str(consumer): [_checker_output([], (user_v1,)), _checker_output([], (user_v2,))],
+                str(report): [_checker_output([], (orders,))],
+            }
+            mock_check_file = MagicMock(side_effect=lambda path, _index: outputs[path].pop(0))
+            current_hashes = [
+                {"schema": schema, "file": file, "hash": hash_} for schema, file, hash_ in (user_v2, orders)
+            ]
+            checker = MagicMock(
+                check_file_with_dependencies=mock_check_file,
+                build_project_index=MagicMock(return_value=b"index"),
+                update_project_index=MagicMock(return_value=b"updated-index"),
+                schema_hashes=MagicMock(return_value=json.dumps(current_hashes)),
+            )
+            new_plugin = TypedFramesPlugin(Options())
+
+            with patch.dict(sys.modules, {"typedframes._rust_checker": checker}):
+                for path in (schemas, consumer, report):
+                    new_plugin._run_checker(str(path))
+                first_run = CacheStats(**vars(new_plugin.cache_stats))
+                for path in (schemas, consumer, report):
+                    new_plugin._run_checker(str(path))
+                second_run = CacheStats(**vars(new_plugin.cache_stats))
+
+                # act
+                schemas.write_text("class UserSchema(BaseSchema):\n    mail = Column(type=str)\n")
+                for path in (schemas, consumer, report):
+                    new_plugin._run_checker(str(path))
+
+        # assert
+        self.assertEqual(first_run, CacheStats(hits=0, misses=3, invalidations=0))
+        self.assertEqual(second_run, CacheStats(hits=3, misses=3, invalidations=0))
+        self.assertEqual(new_plugin.cache_stats, CacheStats(hits=4, misses=5, invalidations=1))
+        self.assertEqual(
+            [call.args[0] for call in mock_check_file.call_args_list],
+            [str(schemas), str(consumer), str(report), str(schemas), str(consumer)],
+        )
+
+    def test_should_reload_everything_when_pyproject_changes(self) -> None:
+        """Test that editing pyproject.toml drops cached results and rebuilds the project index."""
+        # arrange
+        mock_build = MagicMock(side_effect=[b"first", b"second"])
+        mock_check_file = MagicMock(return_value=_checker_output([]))
+        checker = MagicMock(check_file_with_dependencies=mock_check_file, build_project_index=mock_build)
+        new_plugin = TypedFramesPlugin(Options())
+
+        with tempfile.TemporaryDirectory() as tmpdir:
+            root = Path(tmpdir).resolve()
+            pyproject = root / "pyproject.toml"
+            pyproject.write_text("[tool.typedframes]\n")
+            pipeline = root / "pipeline.py"
+            pipeline.write_text("df['a']\n")
+
+            with patch.dict(sys.modules, {"typedframes._rust_checker": checker}):
+                new_plugin._run_checker(str(pipeline))
+
+                # act
+                pyproject.write_text("[tool.typedframes]\nwarnings = false\n")
+                new_plugin._run_checker(str(pipeline))
+
+        # assert
+        self.assertEqual(mock_build.call_count, 2)
+        self.assertEqual(mock_check_file.call_args.args, (str(pipeline), b"second"))